@@ -0,0 +1,526 @@
+use std::{
+    f32::consts::{E, PI},
+    fmt,
+    io::{self, Write},
+};
+
+use rand::{seq::SliceRandom, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Bounds, Chromosome, Gene, Individual, Optimizer};
+
+/// Standard test problem, for comparing operators and optimizers.
+///
+/// Fitness is maximized, so problems that are usually minimized (e.g.
+/// [`Sphere`]) return negated values - their optimum is zero.
+pub trait Problem<G = f32> {
+    fn name(&self) -> &str;
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome<G>;
+
+    fn fitness(&self, chromosome: &Chromosome<G>) -> f32;
+
+    /// Best achievable fitness, if known.
+    fn optimum(&self) -> Option<f32> {
+        None
+    }
+
+    /// Range genes are supposed to stay within, if any - handy for
+    /// [`GeneticAlghoritm::with_bounds()`](crate::GeneticAlghoritm::with_bounds).
+    fn bounds(&self) -> Option<Bounds> {
+        None
+    }
+}
+
+/// `-Σ x²`, over `[-5.12, 5.12]`; unimodal and separable - the easiest one.
+#[derive(Clone, Debug)]
+pub struct Sphere {
+    dimension: usize,
+}
+
+/// `-(10n + Σ (x² - 10 cos(2πx)))`, over `[-5.12, 5.12]`; lots of regularly
+/// spaced local optima around the global one.
+#[derive(Clone, Debug)]
+pub struct Rastrigin {
+    dimension: usize,
+}
+
+/// `-Σ (100 (xᵢ₊₁ - xᵢ²)² + (1 - xᵢ)²)`, over `[-2.048, 2.048]`; the optimum
+/// sits at the bottom of a long, narrow, curved valley.
+#[derive(Clone, Debug)]
+pub struct Rosenbrock {
+    dimension: usize,
+}
+
+/// Ackley's function (negated), over `[-32.768, 32.768]`; a nearly flat
+/// outer region with a deep hole in the middle.
+#[derive(Clone, Debug)]
+pub struct Ackley {
+    dimension: usize,
+}
+
+/// Number of `true` genes; the binary counterpart of [`Sphere`].
+#[derive(Clone, Debug)]
+pub struct OneMax {
+    length: usize,
+}
+
+/// Travelling salesman problem: the chromosome is a permutation of cities,
+/// fitness is the negated length of the (closed) tour.
+#[derive(Clone, Debug)]
+pub struct Tsp {
+    cities: Vec<(f32, f32)>,
+}
+
+/// Individual used by [`BenchmarkRunner`]: a chromosome with fitness
+/// computed by a [`Problem`].
+#[derive(Clone, Debug)]
+pub struct Solution<G = f32> {
+    chromosome: Chromosome<G>,
+    fitness: f32,
+}
+
+/// Runs an optimizer on a problem several times, each time with a different
+/// seed, for a fixed number of generations.
+#[derive(Clone, Debug)]
+pub struct BenchmarkRunner {
+    seeds: Vec<u64>,
+    generations: usize,
+    population_size: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchmarkReport {
+    problem: String,
+    optimum: Option<f32>,
+    runs: Vec<BenchmarkRun>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchmarkRun {
+    seed: u64,
+    curve: Vec<f32>,
+}
+
+impl Sphere {
+    pub fn new(dimension: usize) -> Self {
+        Self { dimension }
+    }
+}
+
+impl Rastrigin {
+    pub fn new(dimension: usize) -> Self {
+        Self { dimension }
+    }
+}
+
+impl Rosenbrock {
+    pub fn new(dimension: usize) -> Self {
+        assert!(dimension >= 2);
+
+        Self { dimension }
+    }
+}
+
+impl Ackley {
+    pub fn new(dimension: usize) -> Self {
+        Self { dimension }
+    }
+}
+
+impl OneMax {
+    pub fn new(length: usize) -> Self {
+        Self { length }
+    }
+}
+
+impl Tsp {
+    pub fn new(cities: Vec<(f32, f32)>) -> Self {
+        assert!(cities.len() >= 2);
+
+        Self { cities }
+    }
+
+    /// Generates `count` cities scattered uniformly over a unit square.
+    pub fn random(rng: &mut dyn RngCore, count: usize) -> Self {
+        Self::new((0..count).map(|_| (rng.gen(), rng.gen())).collect())
+    }
+
+    pub fn cities(&self) -> &[(f32, f32)] {
+        &self.cities
+    }
+}
+
+impl Problem for Sphere {
+    fn name(&self) -> &str {
+        "sphere"
+    }
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome {
+        random_reals(rng, self.dimension, 5.12)
+    }
+
+    fn fitness(&self, chromosome: &Chromosome) -> f32 {
+        -chromosome.iter().map(|x| x * x).sum::<f32>()
+    }
+
+    fn optimum(&self) -> Option<f32> {
+        Some(0.0)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds::global(-5.12, 5.12))
+    }
+}
+
+impl Problem for Rastrigin {
+    fn name(&self) -> &str {
+        "rastrigin"
+    }
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome {
+        random_reals(rng, self.dimension, 5.12)
+    }
+
+    fn fitness(&self, chromosome: &Chromosome) -> f32 {
+        let sum: f32 = chromosome
+            .iter()
+            .map(|x| x * x - 10.0 * (2.0 * PI * x).cos())
+            .sum();
+
+        -(10.0 * chromosome.len() as f32 + sum)
+    }
+
+    fn optimum(&self) -> Option<f32> {
+        Some(0.0)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds::global(-5.12, 5.12))
+    }
+}
+
+impl Problem for Rosenbrock {
+    fn name(&self) -> &str {
+        "rosenbrock"
+    }
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome {
+        random_reals(rng, self.dimension, 2.048)
+    }
+
+    fn fitness(&self, chromosome: &Chromosome) -> f32 {
+        let genes: Vec<_> = chromosome.iter().copied().collect();
+
+        -genes
+            .windows(2)
+            .map(|pair| 100.0 * (pair[1] - pair[0] * pair[0]).powi(2) + (1.0 - pair[0]).powi(2))
+            .sum::<f32>()
+    }
+
+    fn optimum(&self) -> Option<f32> {
+        Some(0.0)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds::global(-2.048, 2.048))
+    }
+}
+
+impl Problem for Ackley {
+    fn name(&self) -> &str {
+        "ackley"
+    }
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome {
+        random_reals(rng, self.dimension, 32.768)
+    }
+
+    fn fitness(&self, chromosome: &Chromosome) -> f32 {
+        let n = chromosome.len() as f32;
+        let squares: f32 = chromosome.iter().map(|x| x * x).sum();
+        let cosines: f32 = chromosome.iter().map(|x| (2.0 * PI * x).cos()).sum();
+
+        -(-20.0 * (-0.2 * (squares / n).sqrt()).exp() - (cosines / n).exp() + 20.0 + E)
+    }
+
+    fn optimum(&self) -> Option<f32> {
+        Some(0.0)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds::global(-32.768, 32.768))
+    }
+}
+
+impl Problem<bool> for OneMax {
+    fn name(&self) -> &str {
+        "one-max"
+    }
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome<bool> {
+        (0..self.length).map(|_| rng.gen()).collect()
+    }
+
+    fn fitness(&self, chromosome: &Chromosome<bool>) -> f32 {
+        chromosome.iter().filter(|&&gene| gene).count() as f32
+    }
+
+    fn optimum(&self) -> Option<f32> {
+        Some(self.length as f32)
+    }
+}
+
+impl Problem<usize> for Tsp {
+    fn name(&self) -> &str {
+        "tsp"
+    }
+
+    fn random_chromosome(&self, rng: &mut dyn RngCore) -> Chromosome<usize> {
+        let mut tour: Vec<_> = (0..self.cities.len()).collect();
+
+        tour.shuffle(rng);
+        tour.into_iter().collect()
+    }
+
+    fn fitness(&self, chromosome: &Chromosome<usize>) -> f32 {
+        let tour: Vec<_> = chromosome.iter().map(|&city| self.cities[city]).collect();
+
+        -tour
+            .iter()
+            .zip(tour.iter().cycle().skip(1))
+            .map(|((x1, y1), (x2, y2))| ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt())
+            .sum::<f32>()
+    }
+}
+
+impl<G> Individual<G> for Solution<G> {
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self {
+            chromosome,
+            fitness: f32::NAN,
+        }
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        &self.chromosome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn is_evaluated(&self) -> bool {
+        !self.fitness.is_nan()
+    }
+}
+
+impl BenchmarkRunner {
+    /// By default, uses ten seeds: `0..10`.
+    pub fn new(generations: usize, population_size: usize) -> Self {
+        assert!(population_size > 0);
+
+        Self {
+            seeds: (0..10).collect(),
+            generations,
+            population_size,
+        }
+    }
+
+    pub fn with_seeds(mut self, seeds: impl IntoIterator<Item = u64>) -> Self {
+        self.seeds = seeds.into_iter().collect();
+
+        assert!(!self.seeds.is_empty());
+
+        self
+    }
+
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    pub fn generations(&self) -> usize {
+        self.generations
+    }
+
+    pub fn population_size(&self) -> usize {
+        self.population_size
+    }
+
+    /// Runs a fresh optimizer (returned by `optimizer`) on `problem` once per
+    /// seed.
+    pub fn run<P, O, G>(&self, problem: &P, mut optimizer: impl FnMut() -> O) -> BenchmarkReport
+    where
+        P: Problem<G> + ?Sized,
+        O: Optimizer<Solution<G>, G>,
+        G: Gene,
+    {
+        let runs = self
+            .seeds
+            .iter()
+            .map(|&seed| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                let mut optimizer = optimizer();
+
+                let mut population: Vec<_> = (0..self.population_size)
+                    .map(|_| Solution::create(problem.random_chromosome(&mut rng)))
+                    .collect();
+
+                let mut curve = Vec::with_capacity(self.generations + 1);
+                let mut best = f32::NEG_INFINITY;
+
+                for generation in 0..=self.generations {
+                    if generation > 0 {
+                        population = optimizer.evolve(&mut rng, &population).0;
+                    }
+
+                    for solution in &mut population {
+                        if !solution.is_evaluated() {
+                            solution.fitness = problem.fitness(&solution.chromosome);
+                        }
+
+                        if solution.fitness > best {
+                            best = solution.fitness;
+                        }
+                    }
+
+                    curve.push(best);
+                }
+
+                BenchmarkRun { seed, curve }
+            })
+            .collect();
+
+        BenchmarkReport {
+            problem: problem.name().into(),
+            optimum: problem.optimum(),
+            runs,
+        }
+    }
+}
+
+impl BenchmarkReport {
+    pub fn problem(&self) -> &str {
+        &self.problem
+    }
+
+    pub fn runs(&self) -> &[BenchmarkRun] {
+        &self.runs
+    }
+
+    /// Best-so-far fitness of each generation, averaged over all runs.
+    pub fn mean_curve(&self) -> Vec<f32> {
+        self.curve_by(|fitnesses| fitnesses.iter().sum::<f32>() / fitnesses.len() as f32)
+    }
+
+    /// Best-so-far fitness of each generation, in the worst run.
+    pub fn min_curve(&self) -> Vec<f32> {
+        self.curve_by(|fitnesses| fitnesses.iter().copied().fold(f32::INFINITY, f32::min))
+    }
+
+    /// Best-so-far fitness of each generation, in the best run.
+    pub fn max_curve(&self) -> Vec<f32> {
+        self.curve_by(|fitnesses| fitnesses.iter().copied().fold(f32::NEG_INFINITY, f32::max))
+    }
+
+    pub fn mean_best_fitness(&self) -> f32 {
+        let fitnesses = self.best_fitnesses();
+
+        fitnesses.iter().sum::<f32>() / fitnesses.len() as f32
+    }
+
+    pub fn std_dev_best_fitness(&self) -> f32 {
+        let fitnesses = self.best_fitnesses();
+        let mean = self.mean_best_fitness();
+
+        let variance = fitnesses
+            .iter()
+            .map(|fitness| (fitness - mean).powi(2))
+            .sum::<f32>()
+            / fitnesses.len() as f32;
+
+        variance.sqrt()
+    }
+
+    /// Share of runs that have got within `tolerance` of the problem's
+    /// optimum; `None` when the optimum isn't known.
+    pub fn success_rate(&self, tolerance: f32) -> Option<f32> {
+        let optimum = self.optimum?;
+
+        let successes = self
+            .best_fitnesses()
+            .into_iter()
+            .filter(|fitness| optimum - fitness <= tolerance)
+            .count();
+
+        Some(successes as f32 / self.runs.len() as f32)
+    }
+
+    /// Writes convergence curves as CSV, one row per generation.
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "generation,mean,min,max")?;
+
+        let curves = self
+            .mean_curve()
+            .into_iter()
+            .zip(self.min_curve())
+            .zip(self.max_curve());
+
+        for (generation, ((mean, min), max)) in curves.enumerate() {
+            writeln!(writer, "{},{},{},{}", generation, mean, min, max)?;
+        }
+
+        writer.flush()
+    }
+
+    fn best_fitnesses(&self) -> Vec<f32> {
+        self.runs.iter().map(BenchmarkRun::best_fitness).collect()
+    }
+
+    fn curve_by(&self, reduce: impl Fn(&[f32]) -> f32) -> Vec<f32> {
+        let len = self.runs[0].curve.len();
+
+        (0..len)
+            .map(|generation| {
+                let fitnesses: Vec<_> = self.runs.iter().map(|run| run.curve[generation]).collect();
+
+                reduce(&fitnesses)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: best={:.4} ± {:.4} over {} runs",
+            self.problem,
+            self.mean_best_fitness(),
+            self.std_dev_best_fitness(),
+            self.runs.len(),
+        )
+    }
+}
+
+impl BenchmarkRun {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Best fitness seen up to each generation, starting with the initial
+    /// population.
+    pub fn curve(&self) -> &[f32] {
+        &self.curve
+    }
+
+    pub fn best_fitness(&self) -> f32 {
+        *self.curve.last().unwrap()
+    }
+}
+
+fn random_reals(rng: &mut dyn RngCore, dimension: usize, limit: f32) -> Chromosome {
+    (0..dimension)
+        .map(|_| rng.gen_range(-limit..=limit))
+        .collect()
+}
@@ -0,0 +1,158 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{Chromosome, Gene};
+
+/// Allowed range of genes - either the same for all genes, or specified
+/// separately for each one.
+///
+/// Per-gene ranges go by position; when a chromosome is longer than the list
+/// of ranges (e.g. after [`InsertionMutation`](crate::InsertionMutation)),
+/// genes past the last range are unbounded and left as they are.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Bounds {
+    Global { min: f32, max: f32 },
+    PerGene { ranges: Vec<(f32, f32)> },
+}
+
+/// What to do with a gene that's ended up outside of its [`Bounds`].
+///
+/// Non-finite genes (NaNs and infinities) are always resampled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repair {
+    /// Moves the gene onto the nearest bound.
+    #[default]
+    Clamp,
+
+    /// Bounces the gene off the bound it's crossed, as if it was a mirror.
+    Reflect,
+
+    /// Brings the gene back from the opposite bound, as if the range was
+    /// a circle.
+    Wrap,
+
+    /// Replaces the gene with a random one from the range.
+    Resample,
+}
+
+/// Penalty for violating a constraint (the bigger, the worse), subtracted
+/// from fitness before selection and choosing elites.
+///
+/// Implemented for closures, so `|chromosome: &Chromosome| ...` works.
+pub trait Penalty<G = f32>: Send + Sync {
+    fn penalty(&self, chromosome: &Chromosome<G>) -> f32;
+}
+
+impl<G, F> Penalty<G> for F
+where
+    F: Fn(&Chromosome<G>) -> f32 + Send + Sync,
+{
+    fn penalty(&self, chromosome: &Chromosome<G>) -> f32 {
+        self(chromosome)
+    }
+}
+
+impl Bounds {
+    pub fn global(min: f32, max: f32) -> Self {
+        Self::Global { min, max }
+    }
+
+    pub fn per_gene(ranges: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        Self::PerGene {
+            ranges: ranges.into_iter().collect(),
+        }
+    }
+
+    /// Returns the `(min, max)` range of given gene, or `None` if the gene
+    /// is past the last per-gene range.
+    pub fn range(&self, gene: usize) -> Option<(f32, f32)> {
+        match self {
+            Self::Global { min, max } => Some((*min, *max)),
+            Self::PerGene { ranges } => ranges.get(gene).copied(),
+        }
+    }
+
+    /// Returns the first range that's empty or not finite, if any.
+    pub(crate) fn invalid_range(&self) -> Option<(f32, f32)> {
+        let is_valid = |&(min, max): &(f32, f32)| min.is_finite() && max.is_finite() && min <= max;
+
+        match self {
+            Self::Global { min, max } => Some((*min, *max)).filter(|range| !is_valid(range)),
+            Self::PerGene { ranges } => ranges.iter().copied().find(|range| !is_valid(range)),
+        }
+    }
+
+    /// Brings all genes of `chromosome` back into bounds.
+    pub fn repair<G>(&self, rng: &mut dyn RngCore, repair: Repair, chromosome: &mut Chromosome<G>)
+    where
+        G: Gene,
+    {
+        for (idx, gene) in chromosome.iter_mut().enumerate() {
+            let (min, max) = match self.range(idx) {
+                Some(range) => range,
+                None => break,
+            };
+            let value = gene.to_f32();
+
+            if (min..=max).contains(&value) {
+                continue;
+            }
+
+            *gene = G::from_f32(repair.apply(rng, value, min, max));
+        }
+    }
+}
+
+impl Repair {
+    fn apply(self, rng: &mut dyn RngCore, value: f32, min: f32, max: f32) -> f32 {
+        let range = max - min;
+
+        if range == 0.0 {
+            return min;
+        }
+
+        if !value.is_finite() {
+            return rng.gen_range(min..=max);
+        }
+
+        match self {
+            Self::Clamp => value.clamp(min, max),
+
+            Self::Reflect => {
+                let offset = (value - min).rem_euclid(2.0 * range);
+
+                if offset > range {
+                    max - (offset - range)
+                } else {
+                    min + offset
+                }
+            }
+
+            Self::Wrap => min + (value - min).rem_euclid(range),
+            Self::Resample => rng.gen_range(min..=max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::rng;
+
+    #[test]
+    fn genes_past_per_gene_ranges_are_unbounded() {
+        let bounds = Bounds::per_gene(vec![(0.0, 1.0), (-1.0, 0.0)]);
+
+        let mut long: Chromosome = [5.0, 5.0, 5.0].iter().copied().collect();
+        bounds.repair(&mut rng(), Repair::Clamp, &mut long);
+        assert_eq!(long.iter().copied().collect::<Vec<_>>(), [1.0, 0.0, 5.0]);
+
+        let mut short: Chromosome = [-5.0].iter().copied().collect();
+        bounds.repair(&mut rng(), Repair::Clamp, &mut short);
+        assert_eq!(short.iter().copied().collect::<Vec<_>>(), [0.0]);
+
+        assert_eq!(bounds.range(2), None);
+    }
+}
@@ -0,0 +1,261 @@
+use std::{error::Error, fmt};
+
+use crate::{
+    Bounds, CrossoverMethod, Gene, GeneticAlghoritm, HallOfFame, Mating, MutationMethod,
+    NoveltySearch, Observer, Penalty, RandomImmigrants, Repair, SelectionMethod, Speciation,
+};
+
+/// Validated way of configuring [`GeneticAlghoritm`] - instead of
+/// panicking, `build()` reports what's wrong with the configuration.
+pub struct GeneticAlgorithmBuilder<S, G = f32> {
+    selection_method: Option<S>,
+    crossover_method: Option<Box<dyn CrossoverMethod<G>>>,
+    mutation_method: Option<Box<dyn MutationMethod<G>>>,
+    elite_count: usize,
+    crossover_probability: f32,
+    mating: Mating,
+    population_size: Option<usize>,
+    speciation: Option<Speciation>,
+    bounds: Option<Bounds>,
+    repair: Repair,
+    penalty: Option<Box<dyn Penalty<G>>>,
+    hall_of_fame: Option<HallOfFame<G>>,
+    novelty_search: Option<NoveltySearch>,
+    immigrants: Option<RandomImmigrants<G>>,
+    observers: Vec<Box<dyn Observer<G>>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    MissingSelection,
+    MissingCrossover,
+    MissingMutation,
+    InvalidPopulationSize,
+    InvalidSpeciationThreshold(f32),
+    InvalidBounds {
+        min: f32,
+        max: f32,
+    },
+    TooManyElites {
+        elite_count: usize,
+        population_size: usize,
+    },
+    InvalidCrossoverProbability(f32),
+    InvalidParameter {
+        name: &'static str,
+        value: f32,
+    },
+}
+
+impl<S, G> GeneticAlgorithmBuilder<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    pub fn new() -> Self {
+        Self {
+            selection_method: None,
+            crossover_method: None,
+            mutation_method: None,
+            elite_count: 0,
+            crossover_probability: 1.0,
+            mating: Mating::default(),
+            population_size: None,
+            speciation: None,
+            bounds: None,
+            repair: Repair::default(),
+            penalty: None,
+            hall_of_fame: None,
+            novelty_search: None,
+            immigrants: None,
+            observers: Vec::new(),
+        }
+    }
+
+    pub fn selection(mut self, selection_method: S) -> Self {
+        self.selection_method = Some(selection_method);
+        self
+    }
+
+    pub fn crossover(mut self, crossover_method: impl CrossoverMethod<G> + 'static) -> Self {
+        self.crossover_method = Some(Box::new(crossover_method));
+        self
+    }
+
+    pub fn mutation(mut self, mutation_method: impl MutationMethod<G> + 'static) -> Self {
+        self.mutation_method = Some(Box::new(mutation_method));
+        self
+    }
+
+    /// See: [`GeneticAlghoritm::with_elitism()`].
+    pub fn elitism(mut self, elite_count: usize) -> Self {
+        self.elite_count = elite_count;
+        self
+    }
+
+    /// Chance of a child being created through crossover; otherwise it
+    /// starts as a copy of one of its parents (mutation applies either
+    /// way).
+    pub fn crossover_probability(mut self, probability: f32) -> Self {
+        self.crossover_probability = probability;
+        self
+    }
+
+    /// See: [`GeneticAlghoritm::population_size()`].
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        self.population_size = Some(population_size);
+        self
+    }
+
+    /// See: [`Mating`].
+    pub fn mating(mut self, mating: Mating) -> Self {
+        self.mating = mating;
+        self
+    }
+
+    /// See: [`Speciation`].
+    pub fn speciation(mut self, speciation: Speciation) -> Self {
+        self.speciation = Some(speciation);
+        self
+    }
+
+    /// See: [`GeneticAlghoritm::with_bounds()`].
+    pub fn bounds(mut self, bounds: Bounds, repair: Repair) -> Self {
+        self.bounds = Some(bounds);
+        self.repair = repair;
+        self
+    }
+
+    /// See: [`Penalty`].
+    pub fn penalty(mut self, penalty: impl Penalty<G> + 'static) -> Self {
+        self.penalty = Some(Box::new(penalty));
+        self
+    }
+
+    /// See: [`HallOfFame`].
+    pub fn hall_of_fame(mut self, hall_of_fame: HallOfFame<G>) -> Self {
+        self.hall_of_fame = Some(hall_of_fame);
+        self
+    }
+
+    /// See: [`NoveltySearch`].
+    pub fn novelty_search(mut self, novelty_search: NoveltySearch) -> Self {
+        self.novelty_search = Some(novelty_search);
+        self
+    }
+
+    /// See: [`RandomImmigrants`].
+    pub fn random_immigrants(mut self, immigrants: RandomImmigrants<G>) -> Self {
+        self.immigrants = Some(immigrants);
+        self
+    }
+
+    /// See: [`Observer`].
+    pub fn observer(mut self, observer: impl Observer<G> + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn build(self) -> Result<GeneticAlghoritm<S, G>, ConfigError> {
+        let selection_method = self.selection_method.ok_or(ConfigError::MissingSelection)?;
+        let crossover_method = self.crossover_method.ok_or(ConfigError::MissingCrossover)?;
+        let mutation_method = self.mutation_method.ok_or(ConfigError::MissingMutation)?;
+
+        if !(0.0..=1.0).contains(&self.crossover_probability) {
+            return Err(ConfigError::InvalidCrossoverProbability(
+                self.crossover_probability,
+            ));
+        }
+
+        if let Some((min, max)) = self.bounds.as_ref().and_then(Bounds::invalid_range) {
+            return Err(ConfigError::InvalidBounds { min, max });
+        }
+
+        if let Some(population_size) = self.population_size {
+            if population_size == 0 {
+                return Err(ConfigError::InvalidPopulationSize);
+            }
+
+            if self.elite_count > population_size {
+                return Err(ConfigError::TooManyElites {
+                    elite_count: self.elite_count,
+                    population_size,
+                });
+            }
+        }
+
+        Ok(GeneticAlghoritm {
+            selection_method,
+            crossover_method,
+            mutation_method,
+            elite_count: self.elite_count,
+            crossover_probability: self.crossover_probability,
+            mating: self.mating,
+            population_size: self.population_size,
+            speciation: self.speciation,
+            bounds: self.bounds,
+            repair: self.repair,
+            penalty: self.penalty,
+            hall_of_fame: self.hall_of_fame,
+            novelty_search: self.novelty_search,
+            immigrants: self.immigrants,
+            observers: self.observers,
+            generation: 0,
+            last_max_fitness: None,
+        })
+    }
+}
+
+impl<S, G> Default for GeneticAlgorithmBuilder<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSelection => write!(f, "selection method is not set"),
+            Self::MissingCrossover => write!(f, "crossover method is not set"),
+            Self::MissingMutation => write!(f, "mutation method is not set"),
+            Self::InvalidPopulationSize => write!(f, "population size must be positive"),
+
+            Self::InvalidSpeciationThreshold(threshold) => write!(
+                f,
+                "speciation threshold must be positive, got {}",
+                threshold
+            ),
+
+            Self::InvalidBounds { min, max } => write!(
+                f,
+                "bounds must be finite, with min <= max, got <{}, {}>",
+                min, max
+            ),
+
+            Self::TooManyElites {
+                elite_count,
+                population_size,
+            } => write!(
+                f,
+                "elite count ({}) exceeds population size ({})",
+                elite_count, population_size
+            ),
+
+            Self::InvalidCrossoverProbability(probability) => write!(
+                f,
+                "crossover probability must be in range <0, 1>, got {}",
+                probability
+            ),
+
+            Self::InvalidParameter { name, value } => {
+                write!(f, "invalid value of `{}`: {}", name, value)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
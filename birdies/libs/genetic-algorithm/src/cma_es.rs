@@ -0,0 +1,296 @@
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
+use rand::RngCore;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::{fittest, Bounds, Chromosome, Individual, Optimizer, Repair, Statistics};
+
+/// Covariance matrix adaptation evolution strategy (CMA-ES) - samples each
+/// population from a multivariate normal distribution, then moves the
+/// distribution's mean towards the fittest half of the samples and learns
+/// its shape (covariance) and size (step size) from the way they've moved.
+///
+/// Population size is taken from the population passed to `evolve()`; the
+/// first call only places the mean, the following ones adapt the rest.
+pub struct CmaEs {
+    initial_sigma: f32,
+    bounds: Option<Bounds>,
+    repair: Repair,
+    state: Option<CmaState>,
+    last_max_fitness: Option<f32>,
+}
+
+struct CmaState {
+    mean: DVector<f32>,
+    sigma: f32,
+    covariance: DMatrix<f32>,
+    /// Eigenvectors of `covariance`.
+    b: DMatrix<f32>,
+    /// Square roots of the eigenvalues of `covariance`.
+    d: DVector<f32>,
+    p_sigma: DVector<f32>,
+    p_c: DVector<f32>,
+    generation: i32,
+}
+
+/// Learning rates and weights, derived from the problem's dimension and
+/// population size as recommended by Hansen's "The CMA Evolution Strategy:
+/// A Tutorial".
+struct CmaParams {
+    weights: Vec<f32>,
+    mu_eff: f32,
+    c_sigma: f32,
+    d_sigma: f32,
+    c_c: f32,
+    c_1: f32,
+    c_mu: f32,
+    /// Expected length of an `N(0, I)`-distributed vector.
+    chi_n: f32,
+}
+
+impl CmaEs {
+    /// `sigma` is the initial step size - roughly, how far from the mean the
+    /// optimum is expected to be.
+    pub fn new(sigma: f32) -> Self {
+        assert!(sigma > 0.0);
+
+        Self {
+            initial_sigma: sigma,
+            bounds: None,
+            repair: Repair::default(),
+            state: None,
+            last_max_fitness: None,
+        }
+    }
+
+    /// Keeps samples within `bounds` - see [`Bounds`].
+    pub fn with_bounds(mut self, bounds: Bounds, repair: Repair) -> Self {
+        self.bounds = Some(bounds);
+        self.repair = repair;
+        self
+    }
+
+    /// Current step size.
+    pub fn sigma(&self) -> f32 {
+        self.state
+            .as_ref()
+            .map_or(self.initial_sigma, |state| state.sigma)
+    }
+
+    /// Current mean of the distribution, once it's been placed.
+    pub fn mean(&self) -> Option<&[f32]> {
+        self.state.as_ref().map(|state| state.mean.as_slice())
+    }
+
+    fn update<I>(&mut self, population: &[I])
+    where
+        I: Individual,
+    {
+        let dimension = population[0].chromosome().len();
+        let params = CmaParams::new(dimension, population.len());
+        let fitnesses: Vec<_> = population.iter().map(Individual::fitness).collect();
+
+        let parents: Vec<_> = fittest(&fitnesses, params.weights.len())
+            .into_iter()
+            .map(|idx| {
+                let chromosome = population[idx].chromosome();
+
+                assert_eq!(
+                    chromosome.len(),
+                    dimension,
+                    "all chromosomes should have the same length"
+                );
+
+                DVector::from_iterator(dimension, chromosome.iter().copied())
+            })
+            .collect();
+
+        let recombine = |vectors: &[DVector<f32>]| {
+            vectors
+                .iter()
+                .zip(&params.weights)
+                .fold(DVector::zeros(dimension), |sum, (vector, &weight)| {
+                    sum + vector * weight
+                })
+        };
+
+        let state = match &mut self.state {
+            Some(state) => state,
+
+            None => {
+                self.state = Some(CmaState::new(recombine(&parents), self.initial_sigma));
+                return;
+            }
+        };
+
+        state.generation += 1;
+
+        let steps: Vec<_> = parents
+            .iter()
+            .map(|parent| (parent - &state.mean) / state.sigma)
+            .collect();
+
+        let step = recombine(&steps);
+
+        state.mean += &step * state.sigma;
+
+        // C^(-1/2) * step
+        let whitened = &state.b * (state.b.transpose() * &step).component_div(&state.d);
+
+        state.p_sigma = &state.p_sigma * (1.0 - params.c_sigma)
+            + whitened * (params.c_sigma * (2.0 - params.c_sigma) * params.mu_eff).sqrt();
+
+        let p_sigma_norm = state.p_sigma.norm();
+
+        let h_sigma = p_sigma_norm
+            / (1.0 - (1.0 - params.c_sigma).powi(2 * state.generation)).sqrt()
+            < (1.4 + 2.0 / (dimension as f32 + 1.0)) * params.chi_n;
+
+        let h_sigma = if h_sigma { 1.0 } else { 0.0 };
+
+        state.p_c = &state.p_c * (1.0 - params.c_c)
+            + &step * (h_sigma * (params.c_c * (2.0 - params.c_c) * params.mu_eff).sqrt());
+
+        let rank_one = &state.p_c * state.p_c.transpose()
+            + &state.covariance * ((1.0 - h_sigma) * params.c_c * (2.0 - params.c_c));
+
+        let rank_mu = steps.iter().zip(&params.weights).fold(
+            DMatrix::zeros(dimension, dimension),
+            |sum, (step, &weight)| sum + step * step.transpose() * weight,
+        );
+
+        state.covariance = &state.covariance * (1.0 - params.c_1 - params.c_mu)
+            + rank_one * params.c_1
+            + rank_mu * params.c_mu;
+
+        state.sigma *=
+            ((params.c_sigma / params.d_sigma) * (p_sigma_norm / params.chi_n - 1.0)).exp();
+
+        state.decompose();
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> Chromosome {
+        let state = self.state.as_ref().unwrap();
+        let dimension = state.mean.len();
+
+        let z = DVector::from_fn(dimension, |_, _| StandardNormal.sample(rng));
+        let x = &state.mean + &state.b * z.component_mul(&state.d) * state.sigma;
+
+        let mut chromosome: Chromosome = x.iter().copied().collect();
+
+        if let Some(bounds) = &self.bounds {
+            bounds.repair(rng, self.repair, &mut chromosome);
+        }
+
+        chromosome
+    }
+}
+
+impl<I> Optimizer<I> for CmaEs
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(population.len() >= 2, "CMA-ES needs at least 2 individuals");
+
+        let stats = Statistics::new(population, self.last_max_fitness);
+
+        self.last_max_fitness = Some(stats.max_fitness());
+        self.update(population);
+
+        let new_population: Vec<_> = (0..population.len())
+            .map(|_| I::create(self.sample(rng)))
+            .collect();
+
+        let stats = stats.with_offspring(&new_population);
+
+        (new_population, stats)
+    }
+}
+
+impl CmaState {
+    fn new(mean: DVector<f32>, sigma: f32) -> Self {
+        let dimension = mean.len();
+
+        Self {
+            mean,
+            sigma,
+            covariance: DMatrix::identity(dimension, dimension),
+            b: DMatrix::identity(dimension, dimension),
+            d: DVector::repeat(dimension, 1.0),
+            p_sigma: DVector::zeros(dimension),
+            p_c: DVector::zeros(dimension),
+            generation: 0,
+        }
+    }
+
+    /// Refreshes `b` and `d` after `covariance` has changed.
+    fn decompose(&mut self) {
+        // Rounding errors make the matrix drift away from being symmetric
+        self.covariance = (&self.covariance + self.covariance.transpose()) * 0.5;
+
+        let eigen = SymmetricEigen::new(self.covariance.clone());
+
+        self.b = eigen.eigenvectors;
+        self.d = eigen
+            .eigenvalues
+            .map(|value| value.max(f32::EPSILON).sqrt());
+    }
+}
+
+impl CmaParams {
+    fn new(dimension: usize, population_size: usize) -> Self {
+        let n = dimension as f32;
+        let mu = population_size / 2;
+
+        let weights: Vec<_> = (1..=mu)
+            .map(|i| (mu as f32 + 0.5).ln() - (i as f32).ln())
+            .collect();
+
+        let sum: f32 = weights.iter().sum();
+        let weights: Vec<_> = weights.into_iter().map(|weight| weight / sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|weight| weight * weight).sum::<f32>();
+
+        let c_sigma = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let c_1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+
+        let c_mu =
+            (1.0 - c_1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff));
+
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self {
+            weights,
+            mu_eff,
+            c_sigma,
+            d_sigma,
+            c_c,
+            c_1,
+            c_mu,
+            chi_n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{population, rng};
+    use crate::{BenchmarkRunner, Sphere};
+
+    #[test]
+    fn converges_on_sphere() {
+        let report = BenchmarkRunner::new(100, 12)
+            .with_seeds(0..3)
+            .run(&Sphere::new(4), || CmaEs::new(2.0));
+
+        assert_eq!(report.success_rate(1e-4), Some(1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "CMA-ES needs at least 2 individuals")]
+    fn rejects_too_small_population() {
+        CmaEs::new(1.0).evolve(&mut rng(), &population(&[1.0]));
+    }
+}
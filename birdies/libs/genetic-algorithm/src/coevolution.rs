@@ -0,0 +1,293 @@
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::{fittest, Chromosome, Gene, GeneticAlghoritm, Individual, SelectionMethod, Statistics};
+
+/// Competitive coevolution: two populations (say, predators and prey), each
+/// evolved by its own [`GeneticAlghoritm`], with fitness of each individual
+/// coming from games played against opponents drawn from the other
+/// population.
+///
+/// Opponents are drawn once per generation and shared by the entire
+/// population, so that everyone gets judged against the same ones.
+pub struct Coevolution<SA, SB, GA = f32, GB = f32> {
+    a: GeneticAlghoritm<SA, GA>,
+    b: GeneticAlghoritm<SB, GB>,
+    sampling_a: OpponentSampling,
+    sampling_b: OpponentSampling,
+    archive_size: usize,
+    archive_a: Vec<Chromosome<GA>>,
+    archive_b: Vec<Chromosome<GB>>,
+    generation: usize,
+}
+
+/// Individual going through [`Coevolution::evolve()`]: either a parent,
+/// with fitness coming from its games, or a newly created child.
+enum Competitor<'a, I> {
+    Parent { individual: &'a I, fitness: f32 },
+    Child(I),
+}
+
+/// Where opponents come from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpponentSampling {
+    /// The fittest individual of the other population's previous
+    /// generation (in the first generation - a random one).
+    BestOfLastGeneration,
+
+    /// `count` random individuals of the other population's current
+    /// generation.
+    Random { count: usize },
+
+    /// `count` random champions out of the other population's archive of
+    /// past champions (the fittest individual of each generation) - which
+    /// keeps populations from forgetting how to beat old strategies; falls
+    /// back to [`Self::Random`] while the archive is empty.
+    HallOfFame { count: usize },
+}
+
+impl<SA, SB, GA, GB> Coevolution<SA, SB, GA, GB>
+where
+    SA: SelectionMethod,
+    SB: SelectionMethod,
+    GA: Gene,
+    GB: Gene,
+{
+    /// By default, each individual plays against five random opponents and
+    /// archives hold up to 100 champions.
+    pub fn new(a: GeneticAlghoritm<SA, GA>, b: GeneticAlghoritm<SB, GB>) -> Self {
+        Self {
+            a,
+            b,
+            sampling_a: OpponentSampling::Random { count: 5 },
+            sampling_b: OpponentSampling::Random { count: 5 },
+            archive_size: 100,
+            archive_a: Vec::new(),
+            archive_b: Vec::new(),
+            generation: 0,
+        }
+    }
+
+    /// Sets where opponents of population A (drawn from population B) and
+    /// of population B (drawn from population A) come from.
+    pub fn with_sampling(
+        mut self,
+        sampling_a: OpponentSampling,
+        sampling_b: OpponentSampling,
+    ) -> Self {
+        for sampling in [sampling_a, sampling_b] {
+            if let OpponentSampling::Random { count } | OpponentSampling::HallOfFame { count } =
+                sampling
+            {
+                assert!(count > 0);
+            }
+        }
+
+        self.sampling_a = sampling_a;
+        self.sampling_b = sampling_b;
+        self
+    }
+
+    /// Sets how many past champions each archive holds at most (after
+    /// which the oldest ones are forgotten).
+    pub fn with_archive_size(mut self, archive_size: usize) -> Self {
+        assert!(archive_size > 0);
+
+        self.archive_size = archive_size;
+        self
+    }
+
+    pub fn a(&self) -> &GeneticAlghoritm<SA, GA> {
+        &self.a
+    }
+
+    pub fn a_mut(&mut self) -> &mut GeneticAlghoritm<SA, GA> {
+        &mut self.a
+    }
+
+    pub fn b(&self) -> &GeneticAlghoritm<SB, GB> {
+        &self.b
+    }
+
+    pub fn b_mut(&mut self) -> &mut GeneticAlghoritm<SB, GB> {
+        &mut self.b
+    }
+
+    /// Champions of population A, oldest first.
+    pub fn archive_a(&self) -> &[Chromosome<GA>] {
+        &self.archive_a
+    }
+
+    /// Champions of population B, oldest first.
+    pub fn archive_b(&self) -> &[Chromosome<GB>] {
+        &self.archive_b
+    }
+
+    /// Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Evaluates both populations against each other, then evolves each of
+    /// them; returns the next generations along with statistics of both.
+    ///
+    /// `play` pits an individual of population A against an individual of
+    /// population B and returns both of their scores; fitness of each
+    /// individual is its mean score over all of its opponents
+    /// (`Individual::fitness()` is not used here).
+    pub fn evolve<IA, IB, F>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population_a: &[IA],
+        population_b: &[IB],
+        mut play: F,
+    ) -> (Vec<IA>, Vec<IB>, Statistics, Statistics)
+    where
+        IA: Individual<GA>,
+        IB: Individual<GB>,
+        F: FnMut(&Chromosome<GA>, &Chromosome<GB>) -> (f32, f32),
+    {
+        assert!(!population_a.is_empty());
+        assert!(!population_b.is_empty());
+
+        let opponents_a = opponents(rng, self.sampling_a, population_b, &self.archive_b);
+        let opponents_b = opponents(rng, self.sampling_b, population_a, &self.archive_a);
+
+        let population_a: Vec<_> = population_a
+            .iter()
+            .map(|individual| Competitor::Parent {
+                individual,
+                fitness: mean(
+                    opponents_a
+                        .iter()
+                        .map(|opponent| play(individual.chromosome(), opponent).0),
+                ),
+            })
+            .collect();
+
+        let population_b: Vec<_> = population_b
+            .iter()
+            .map(|individual| Competitor::Parent {
+                individual,
+                fitness: mean(
+                    opponents_b
+                        .iter()
+                        .map(|opponent| play(opponent, individual.chromosome()).1),
+                ),
+            })
+            .collect();
+
+        archive(&mut self.archive_a, &population_a, self.archive_size);
+        archive(&mut self.archive_b, &population_b, self.archive_size);
+
+        let (offspring_a, stats_a) = self.a.evolve(rng, &population_a);
+        let (offspring_b, stats_b) = self.b.evolve(rng, &population_b);
+
+        self.generation += 1;
+
+        (unwrap(offspring_a), unwrap(offspring_b), stats_a, stats_b)
+    }
+}
+
+impl<I, G> Individual<G> for Competitor<'_, I>
+where
+    I: Individual<G>,
+{
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self::Child(I::create(chromosome))
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        match self {
+            Self::Parent { individual, .. } => individual.chromosome(),
+            Self::Child(individual) => individual.chromosome(),
+        }
+    }
+
+    fn fitness(&self) -> f32 {
+        match self {
+            Self::Parent { fitness, .. } => *fitness,
+            Self::Child(_) => f32::NAN,
+        }
+    }
+
+    fn is_evaluated(&self) -> bool {
+        matches!(self, Self::Parent { .. })
+    }
+
+    fn behaviour(&self) -> Option<Vec<f32>> {
+        match self {
+            Self::Parent { individual, .. } => individual.behaviour(),
+            Self::Child(individual) => individual.behaviour(),
+        }
+    }
+}
+
+/// Draws chromosomes of opponents out of `population` (the current
+/// generation of the other side) or `archive` (its past champions).
+fn opponents<I, G>(
+    rng: &mut dyn RngCore,
+    sampling: OpponentSampling,
+    population: &[I],
+    archive: &[Chromosome<G>],
+) -> Vec<Chromosome<G>>
+where
+    I: Individual<G>,
+    G: Gene,
+{
+    let sample = |rng: &mut dyn RngCore, count: usize| {
+        population
+            .choose_multiple(rng, count)
+            .map(|individual| individual.chromosome().clone())
+            .collect()
+    };
+
+    match sampling {
+        OpponentSampling::BestOfLastGeneration => match archive.last() {
+            Some(champion) => vec![champion.clone()],
+            None => sample(rng, 1),
+        },
+
+        OpponentSampling::Random { count } => sample(rng, count),
+
+        OpponentSampling::HallOfFame { count } => {
+            if archive.is_empty() {
+                sample(rng, count)
+            } else {
+                archive.choose_multiple(rng, count).cloned().collect()
+            }
+        }
+    }
+}
+
+/// Remembers the fittest individual of `population` as a champion.
+fn archive<I, G>(archive: &mut Vec<Chromosome<G>>, population: &[Competitor<I>], size: usize)
+where
+    I: Individual<G>,
+    G: Gene,
+{
+    let fitnesses: Vec<_> = population.iter().map(Individual::fitness).collect();
+    let champion = fittest(&fitnesses, 1)[0];
+
+    archive.push(population[champion].chromosome().clone());
+
+    if archive.len() > size {
+        archive.drain(..(archive.len() - size));
+    }
+}
+
+fn mean(scores: impl Iterator<Item = f32>) -> f32 {
+    let (sum, count) = scores.fold((0.0, 0), |(sum, count), score| (sum + score, count + 1));
+
+    sum / count as f32
+}
+
+/// Brings children back from the [`Competitor`] wrappers.
+fn unwrap<I>(population: Vec<Competitor<I>>) -> Vec<I> {
+    population
+        .into_iter()
+        .map(|competitor| match competitor {
+            Competitor::Child(individual) => individual,
+            Competitor::Parent { .. } => unreachable!("evolve() creates all individuals anew"),
+        })
+        .collect()
+}
@@ -0,0 +1,384 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Serializable description of a [`GeneticAlghoritm`], e.g.:
+///
+/// ```json
+/// {
+///     "selection": { "kind": "roulette_wheel" },
+///     "crossover": { "kind": "uniform" },
+///     "mutation": { "kind": "gaussian", "chance": 0.01, "coeff": 0.3 },
+///     "elite_count": 2,
+///     "bounds": { "kind": "global", "min": -1.0, "max": 1.0 },
+///     "repair": "reflect"
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeneticAlgorithmConfig {
+    #[serde(default)]
+    pub selection: SelectionConfig,
+    #[serde(default)]
+    pub crossover: CrossoverConfig,
+    pub mutation: MutationConfig,
+    #[serde(default)]
+    pub elite_count: usize,
+    #[serde(default = "default_crossover_probability")]
+    pub crossover_probability: f32,
+    #[serde(default)]
+    pub mating: Mating,
+    #[serde(default)]
+    pub population_size: Option<usize>,
+    /// See: [`Speciation::new()`].
+    #[serde(default)]
+    pub speciation_threshold: Option<f32>,
+    #[serde(default)]
+    pub bounds: Option<Bounds>,
+    #[serde(default)]
+    pub repair: Repair,
+    /// See: [`HallOfFame::new()`].
+    #[serde(default)]
+    pub hall_of_fame_size: Option<usize>,
+    /// See: [`HallOfFame::with_min_distance()`].
+    #[serde(default)]
+    pub hall_of_fame_min_distance: f32,
+    /// See: [`NoveltySearch::new()`].
+    #[serde(default)]
+    pub novelty_weight: Option<f32>,
+    /// See: [`NoveltySearch::new()`].
+    #[serde(default = "default_novelty_neighbours")]
+    pub novelty_neighbours: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SelectionConfig {
+    #[default]
+    RouletteWheel,
+    ScaledRouletteWheel {
+        scaling: FitnessScaling,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CrossoverConfig {
+    #[default]
+    Uniform,
+    SinglePoint,
+    KPoint {
+        k: usize,
+    },
+    Arithmetic {
+        #[serde(default)]
+        weight: Option<f32>,
+    },
+    Blend {
+        alpha: f32,
+    },
+    SimulatedBinary {
+        eta: f32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MutationConfig {
+    Gaussian {
+        chance: f32,
+        coeff: f32,
+    },
+    Normal {
+        chance: f32,
+        sigma: f32,
+    },
+    OneFifthRule {
+        chance: f32,
+        sigma: f32,
+        #[serde(default)]
+        factor: Option<f32>,
+        #[serde(default)]
+        window: Option<usize>,
+    },
+    Decaying {
+        chance: f32,
+        sigma: f32,
+        decay: f32,
+        min_sigma: f32,
+    },
+}
+
+impl GeneticAlgorithmConfig {
+    pub fn builder(&self) -> Result<GeneticAlgorithmBuilder<RouletteWheelSelection>, ConfigError> {
+        let builder = GeneticAlgorithmBuilder::new()
+            .selection(self.selection.build()?)
+            .elitism(self.elite_count)
+            .crossover_probability(self.crossover_probability)
+            .mating(self.mating);
+
+        if let Some(candidates) = self.mating.candidates() {
+            check("mating_candidates", candidates as f32, candidates > 0)?;
+        }
+
+        let builder = self.crossover.apply(builder)?;
+        let builder = self.mutation.apply(builder)?;
+
+        let builder = match self.population_size {
+            Some(population_size) => builder.population_size(population_size),
+            None => builder,
+        };
+
+        let builder = match self.speciation_threshold {
+            Some(threshold) if threshold > 0.0 => builder.speciation(Speciation::new(threshold)),
+            Some(threshold) => return Err(ConfigError::InvalidSpeciationThreshold(threshold)),
+            None => builder,
+        };
+
+        let builder = match &self.bounds {
+            Some(bounds) => builder.bounds(bounds.clone(), self.repair),
+            None => builder,
+        };
+
+        let builder = match self.novelty_weight {
+            Some(weight) => {
+                check_probability("novelty_weight", weight)?;
+
+                check(
+                    "novelty_neighbours",
+                    self.novelty_neighbours as f32,
+                    self.novelty_neighbours > 0,
+                )?;
+
+                builder.novelty_search(NoveltySearch::new(self.novelty_neighbours, weight))
+            }
+
+            None => builder,
+        };
+
+        Ok(match self.hall_of_fame_size {
+            Some(size) => {
+                check("hall_of_fame_size", size as f32, size > 0)?;
+
+                check(
+                    "hall_of_fame_min_distance",
+                    self.hall_of_fame_min_distance,
+                    self.hall_of_fame_min_distance >= 0.0,
+                )?;
+
+                builder.hall_of_fame(
+                    HallOfFame::new(size).with_min_distance(self.hall_of_fame_min_distance),
+                )
+            }
+
+            None => builder,
+        })
+    }
+
+    pub fn build(&self) -> Result<GeneticAlghoritm<RouletteWheelSelection>, ConfigError> {
+        self.builder()?.build()
+    }
+}
+
+impl SelectionConfig {
+    fn build(&self) -> Result<RouletteWheelSelection, ConfigError> {
+        Ok(match *self {
+            Self::RouletteWheel => RouletteWheelSelection::new(),
+
+            Self::ScaledRouletteWheel { scaling } => {
+                if let Some((name, value)) = scaling.invalid_parameter() {
+                    return Err(ConfigError::InvalidParameter { name, value });
+                }
+
+                RouletteWheelSelection::with_scaling(scaling)
+            }
+        })
+    }
+}
+
+impl CrossoverConfig {
+    fn apply<S>(
+        &self,
+        builder: GeneticAlgorithmBuilder<S>,
+    ) -> Result<GeneticAlgorithmBuilder<S>, ConfigError>
+    where
+        S: SelectionMethod,
+    {
+        Ok(match *self {
+            Self::Uniform => builder.crossover(UniformCrossover::new()),
+            Self::SinglePoint => builder.crossover(SinglePointCrossover::new()),
+
+            Self::KPoint { k } => {
+                check("k", k as f32, k > 0)?;
+                builder.crossover(KPointCrossover::new(k))
+            }
+
+            Self::Arithmetic { weight: None } => builder.crossover(ArithmeticCrossover::new()),
+
+            Self::Arithmetic {
+                weight: Some(weight),
+            } => {
+                check_probability("weight", weight)?;
+                builder.crossover(ArithmeticCrossover::with_weight(weight))
+            }
+
+            Self::Blend { alpha } => {
+                check("alpha", alpha, alpha >= 0.0)?;
+                builder.crossover(BlendCrossover::new(alpha))
+            }
+
+            Self::SimulatedBinary { eta } => {
+                check("eta", eta, eta >= 0.0)?;
+                builder.crossover(SimulatedBinaryCrossover::new(eta))
+            }
+        })
+    }
+}
+
+impl MutationConfig {
+    fn apply<S>(
+        &self,
+        builder: GeneticAlgorithmBuilder<S>,
+    ) -> Result<GeneticAlgorithmBuilder<S>, ConfigError>
+    where
+        S: SelectionMethod,
+    {
+        Ok(match *self {
+            Self::Gaussian { chance, coeff } => {
+                check_probability("chance", chance)?;
+                check("coeff", coeff, coeff.is_finite() && coeff >= 0.0)?;
+                builder.mutation(GaussianMutation::new(chance, coeff))
+            }
+
+            Self::Normal { chance, sigma } => {
+                check_probability("chance", chance)?;
+                check("sigma", sigma, sigma >= 0.0)?;
+                builder.mutation(NormalMutation::new(chance, sigma))
+            }
+
+            Self::OneFifthRule {
+                chance,
+                sigma,
+                factor,
+                window,
+            } => {
+                check_probability("chance", chance)?;
+                check("sigma", sigma, sigma > 0.0)?;
+
+                let mut mutation = OneFifthRuleMutation::new(chance, sigma);
+
+                if let Some(factor) = factor {
+                    check("factor", factor, factor > 0.0 && factor < 1.0)?;
+                    mutation = mutation.with_factor(factor);
+                }
+
+                if let Some(window) = window {
+                    check("window", window as f32, window > 0)?;
+                    mutation = mutation.with_window(window);
+                }
+
+                builder.mutation(mutation)
+            }
+
+            Self::Decaying {
+                chance,
+                sigma,
+                decay,
+                min_sigma,
+            } => {
+                check_probability("chance", chance)?;
+                check("sigma", sigma, sigma >= 0.0)?;
+                check("decay", decay, decay > 0.0 && decay <= 1.0)?;
+                check("min_sigma", min_sigma, min_sigma >= 0.0)?;
+                builder.mutation(DecayingMutation::new(chance, sigma, decay, min_sigma))
+            }
+        })
+    }
+}
+
+fn default_crossover_probability() -> f32 {
+    1.0
+}
+
+fn default_novelty_neighbours() -> usize {
+    15
+}
+
+fn check(name: &'static str, value: f32, valid: bool) -> Result<(), ConfigError> {
+    if valid {
+        Ok(())
+    } else {
+        Err(ConfigError::InvalidParameter { name, value })
+    }
+}
+
+fn check_probability(name: &'static str, value: f32) -> Result<(), ConfigError> {
+    check(name, value, (0.0..=1.0).contains(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(json: &str) -> Result<GeneticAlghoritm<RouletteWheelSelection>, ConfigError> {
+        serde_json::from_str::<GeneticAlgorithmConfig>(json)
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn rejects_scalings_that_reverse_selection() {
+        for (scaling, name, value) in [
+            (
+                r#"{ "kind": "power_law", "exponent": 0.0 }"#,
+                "exponent",
+                0.0,
+            ),
+            (
+                r#"{ "kind": "power_law", "exponent": -1.0 }"#,
+                "exponent",
+                -1.0,
+            ),
+            (
+                r#"{ "kind": "linear", "multiplier": 1.0 }"#,
+                "multiplier",
+                1.0,
+            ),
+            (
+                r#"{ "kind": "linear", "multiplier": 0.5 }"#,
+                "multiplier",
+                0.5,
+            ),
+        ] {
+            let json = format!(
+                r#"{{
+                    "selection": {{ "kind": "scaled_roulette_wheel", "scaling": {} }},
+                    "mutation": {{ "kind": "gaussian", "chance": 0.01, "coeff": 0.3 }}
+                }}"#,
+                scaling
+            );
+
+            assert_eq!(
+                build(&json).err(),
+                Some(ConfigError::InvalidParameter { name, value }),
+                "scaling = {}",
+                scaling
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_negative_gaussian_coeff() {
+        let json = r#"{
+            "selection": { "kind": "roulette_wheel" },
+            "mutation": { "kind": "gaussian", "chance": 0.01, "coeff": -0.3 }
+        }"#;
+
+        assert_eq!(
+            build(json).err(),
+            Some(ConfigError::InvalidParameter {
+                name: "coeff",
+                value: -0.3
+            })
+        );
+    }
+}
@@ -0,0 +1,557 @@
+use rand::{seq::index, Rng, RngCore};
+
+use crate::{Chromosome, Gene};
+
+pub trait CrossoverMethod<G = f32>: Send + Sync {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
+}
+
+/// Picks each gene from either parent with equal probability.
+#[derive(Clone, Debug, Default)]
+pub struct UniformCrossover;
+
+/// Takes genes from `parent_a` up to a random cut point, and from
+/// `parent_b` afterwards.
+#[derive(Clone, Debug, Default)]
+pub struct SinglePointCrossover;
+
+/// Generalization of [`SinglePointCrossover`] - cuts both parents at `k`
+/// random points and alternates between them at each cut.
+#[derive(Clone, Debug)]
+pub struct KPointCrossover {
+    k: usize,
+}
+
+/// Whole arithmetic crossover: `child = w * a + (1 - w) * b`, where `w` is
+/// either fixed or drawn uniformly from `<0, 1>` for each child.
+#[derive(Clone, Debug, Default)]
+pub struct ArithmeticCrossover {
+    weight: Option<f32>,
+}
+
+/// Blend crossover (BLX-α): each gene is drawn uniformly from the range
+/// spanned by both parents, extended by `alpha * distance` on both sides.
+///
+/// `alpha = 0.5` is the usual choice; `alpha = 0.0` never leaves the
+/// parents' hyper-rectangle.
+#[derive(Clone, Debug)]
+pub struct BlendCrossover {
+    alpha: f32,
+}
+
+/// Simulated binary crossover (SBX).
+///
+/// Mimics the spread of single-point crossover on binary strings; the higher
+/// `eta` (distribution index), the closer children stay to their parents
+/// (2 - 20 are the usual picks).
+#[derive(Clone, Debug)]
+pub struct SimulatedBinaryCrossover {
+    eta: f32,
+}
+
+/// Order crossover (OX) for permutations: copies a random segment of
+/// `parent_a`, then fills the remaining positions with the missing genes in
+/// the order they appear in `parent_b` (starting right after the segment).
+///
+/// Both parents must be permutations of the same genes.
+#[derive(Clone, Debug, Default)]
+pub struct OrderCrossover;
+
+/// Partially mapped crossover (PMX) for permutations: copies a random
+/// segment of `parent_a` and takes the rest from `parent_b`, resolving
+/// duplicates through the mapping defined by the segment - so, contrary to
+/// [`OrderCrossover`], genes mostly keep their absolute positions.
+///
+/// Both parents must be permutations of the same genes - otherwise the
+/// mapping can go in circles, which panics.
+#[derive(Clone, Debug, Default)]
+pub struct PartiallyMappedCrossover;
+
+/// Cut-and-splice crossover for variable-length chromosomes: cuts each
+/// parent at its own random point, then joins the head of `parent_a` with
+/// the tail of `parent_b` - so the child can be shorter or longer than
+/// either of them.
+///
+/// With [`Bounds::PerGene`](crate::Bounds::PerGene), the tail's genes are
+/// repaired against the ranges of the positions they've landed on; genes
+/// past the last range stay unbounded.
+#[derive(Clone, Debug, Default)]
+pub struct CutAndSpliceCrossover {
+    max_len: Option<usize>,
+}
+
+/// Alignment-based crossover for variable-length chromosomes: lines both
+/// parents up gene by gene, picks each of the genes they have in common
+/// from either parent with equal probability (like [`UniformCrossover`]),
+/// then inherits the longer parent's extra genes with probability one half.
+///
+/// Genes keep their positions, so the child's length is always one of the
+/// parents' lengths - and with [`Bounds::PerGene`](crate::Bounds::PerGene),
+/// genes past the last range are left unbounded.
+#[derive(Clone, Debug, Default)]
+pub struct AlignedCrossover;
+
+impl UniformCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for UniformCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let parent_a = parent_a.iter();
+        let parent_b = parent_b.iter();
+
+        parent_a
+            .zip(parent_b)
+            .map(|(a, b)| if rng.gen_bool(0.5) { a } else { b })
+            .cloned()
+            .collect()
+    }
+}
+
+impl SinglePointCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for SinglePointCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        k_point_crossover(rng, 1, parent_a, parent_b)
+    }
+}
+
+impl KPointCrossover {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0);
+
+        Self { k }
+    }
+}
+
+impl<G> CrossoverMethod<G> for KPointCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        k_point_crossover(rng, self.k, parent_a, parent_b)
+    }
+}
+
+impl ArithmeticCrossover {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_weight(weight: f32) -> Self {
+        assert!((0.0..=1.0).contains(&weight));
+
+        Self {
+            weight: Some(weight),
+        }
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let weight = self.weight.unwrap_or_else(|| rng.gen());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| weight * a + (1.0 - weight) * b)
+            .collect()
+    }
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let (min, max) = if a < b { (a, b) } else { (b, a) };
+                let spread = self.alpha * (max - min);
+
+                rng.gen_range((min - spread)..=(max + spread))
+            })
+            .collect()
+    }
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0);
+
+        Self { eta }
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let u: f32 = rng.gen();
+
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(exponent)
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+                };
+
+                // SBX creates two symmetric children - we return only one of
+                // them, so pick the side randomly
+                let (a, b) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+
+                0.5 * ((1.0 + beta) * a + (1.0 - beta) * b)
+            })
+            .collect()
+    }
+}
+
+impl OrderCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for OrderCrossover
+where
+    G: Gene + PartialEq,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+
+        if len < 2 {
+            return parent_a.clone();
+        }
+
+        let (start, end) = segment(rng, len);
+        let segment = &parent_a.genes[start..end];
+
+        let rest: Vec<_> = (0..len)
+            .map(|idx| &parent_b[(end + idx) % len])
+            .filter(|gene| !segment.contains(gene))
+            .collect();
+
+        (0..len)
+            .map(|idx| {
+                if (start..end).contains(&idx) {
+                    parent_a[idx].clone()
+                } else {
+                    rest[(idx + len - end) % len].clone()
+                }
+            })
+            .collect()
+    }
+}
+
+impl PartiallyMappedCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for PartiallyMappedCrossover
+where
+    G: Gene + PartialEq,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+
+        if len < 2 {
+            return parent_a.clone();
+        }
+
+        let (start, end) = segment(rng, len);
+
+        (0..len)
+            .map(|idx| {
+                if (start..end).contains(&idx) {
+                    return parent_a[idx].clone();
+                }
+
+                let mut gene = &parent_b[idx];
+                let mut steps = 0;
+
+                // Gene is already taken by the segment - follow the mapping
+                // until we find one that isn't; on permutations, that takes
+                // at most one step per gene of the segment
+                while let Some(pos) = (start..end).find(|&pos| parent_a[pos] == *gene) {
+                    steps += 1;
+
+                    assert!(
+                        steps <= end - start,
+                        "parents should be permutations of the same genes"
+                    );
+
+                    gene = &parent_b[pos];
+                }
+
+                gene.clone()
+            })
+            .collect()
+    }
+}
+
+impl CutAndSpliceCrossover {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Truncates children longer than `max_len`.
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self {
+            max_len: Some(max_len),
+        }
+    }
+}
+
+impl<G> CrossoverMethod<G> for CutAndSpliceCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        let cut_a = rng.gen_range(0..=parent_a.len());
+        let cut_b = rng.gen_range(0..=parent_b.len());
+
+        parent_a.genes[..cut_a]
+            .iter()
+            .chain(&parent_b.genes[cut_b..])
+            .take(self.max_len.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+impl AlignedCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for AlignedCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        let (shorter, longer) = if parent_a.len() <= parent_b.len() {
+            (parent_a, parent_b)
+        } else {
+            (parent_b, parent_a)
+        };
+
+        let len = if rng.gen_bool(0.5) {
+            longer.len()
+        } else {
+            shorter.len()
+        };
+
+        (0..len)
+            .map(|idx| {
+                if idx < shorter.len() && rng.gen_bool(0.5) {
+                    shorter[idx].clone()
+                } else {
+                    longer[idx].clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns a random, non-empty range of positions `<start, end)`.
+pub(crate) fn segment(rng: &mut dyn RngCore, len: usize) -> (usize, usize) {
+    let mut cuts = index::sample(rng, len + 1, 2).into_vec();
+
+    cuts.sort_unstable();
+
+    (cuts[0], cuts[1])
+}
+
+fn k_point_crossover<G>(
+    rng: &mut dyn RngCore,
+    k: usize,
+    parent_a: &Chromosome<G>,
+    parent_b: &Chromosome<G>,
+) -> Chromosome<G>
+where
+    G: Gene,
+{
+    assert_eq!(parent_a.len(), parent_b.len());
+
+    let len = parent_a.len();
+
+    if len < 2 {
+        return if rng.gen_bool(0.5) {
+            parent_a.clone()
+        } else {
+            parent_b.clone()
+        };
+    }
+
+    // Cut points are in range <1, len-1>, so that each cut actually takes
+    // something from both sides
+    let mut cuts: Vec<_> = index::sample(rng, len - 1, k.min(len - 1))
+        .into_iter()
+        .map(|cut| cut + 1)
+        .collect();
+
+    cuts.sort_unstable();
+
+    let mut cuts = cuts.into_iter().peekable();
+    let mut from_a = true;
+
+    (0..len)
+        .map(|idx| {
+            while cuts.next_if(|&cut| cut == idx).is_some() {
+                from_a = !from_a;
+            }
+
+            if from_a {
+                parent_a[idx].clone()
+            } else {
+                parent_b[idx].clone()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    fn assert_permutations(method: &dyn CrossoverMethod<usize>) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for len in [1, 2, 5, 20] {
+            for _ in 0..100 {
+                let mut genes: Vec<_> = (0..len).collect();
+
+                genes.shuffle(&mut rng);
+                let parent_a: Chromosome<usize> = genes.iter().copied().collect();
+
+                genes.shuffle(&mut rng);
+                let parent_b: Chromosome<usize> = genes.iter().copied().collect();
+
+                let mut child: Vec<_> = method
+                    .crossover(&mut rng, &parent_a, &parent_b)
+                    .into_iter()
+                    .collect();
+
+                child.sort_unstable();
+
+                assert_eq!(child, (0..len).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn order_crossover_returns_permutations() {
+        assert_permutations(&OrderCrossover::new());
+    }
+
+    #[test]
+    fn partially_mapped_crossover_returns_permutations() {
+        assert_permutations(&PartiallyMappedCrossover::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "parents should be permutations of the same genes")]
+    fn partially_mapped_crossover_rejects_non_permutations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent: Chromosome<usize> = [1, 1, 1].iter().copied().collect();
+
+        for _ in 0..100 {
+            PartiallyMappedCrossover::new().crossover(&mut rng, &parent, &parent);
+        }
+    }
+}
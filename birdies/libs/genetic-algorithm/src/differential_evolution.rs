@@ -0,0 +1,220 @@
+use rand::{seq::index, Rng, RngCore};
+
+use crate::{fittest, Bounds, Chromosome, Individual, Optimizer, Repair, Statistics};
+
+/// Differential evolution (DE) - for each individual (target), builds a
+/// trial vector by adding a scaled difference of two other individuals to a
+/// base one and crossing the result over with the target; the trial then
+/// replaces the target only if it turns out to be at least as fit.
+///
+/// Since fitness of trials is known only once they've been evaluated, each
+/// `evolve()` returns trials and settles the targets-vs-trials duel during
+/// the next call - so the population passed in should be the one returned
+/// previously, in the same order.
+pub struct DifferentialEvolution {
+    strategy: DeStrategy,
+    differential_weight: f32,
+    crossover_rate: f32,
+    bounds: Option<Bounds>,
+    repair: Repair,
+    targets: Vec<(Chromosome, f32)>,
+    last_max_fitness: Option<f32>,
+}
+
+/// How the base vector is chosen - in the usual DE/x/y/z notation, both
+/// strategies use one difference vector and binomial crossover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeStrategy {
+    /// A random individual - explores more.
+    Rand1Bin,
+
+    /// The fittest individual - converges faster.
+    Best1Bin,
+}
+
+impl DifferentialEvolution {
+    /// Uses the commonly recommended `F = 0.8` and `CR = 0.9`.
+    pub fn new(strategy: DeStrategy) -> Self {
+        Self {
+            strategy,
+            differential_weight: 0.8,
+            crossover_rate: 0.9,
+            bounds: None,
+            repair: Repair::default(),
+            targets: Vec::new(),
+            last_max_fitness: None,
+        }
+    }
+
+    /// Sets `F` - how much of the difference vector gets added to the base
+    /// one.
+    pub fn with_differential_weight(mut self, differential_weight: f32) -> Self {
+        assert!((0.0..=2.0).contains(&differential_weight));
+
+        self.differential_weight = differential_weight;
+        self
+    }
+
+    /// Sets `CR` - probability of each gene of the trial coming from the
+    /// mutant (instead of the target).
+    pub fn with_crossover_rate(mut self, crossover_rate: f32) -> Self {
+        assert!((0.0..=1.0).contains(&crossover_rate));
+
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// Keeps trials within `bounds` - see [`Bounds`].
+    pub fn with_bounds(mut self, bounds: Bounds, repair: Repair) -> Self {
+        self.bounds = Some(bounds);
+        self.repair = repair;
+        self
+    }
+
+    pub fn strategy(&self) -> DeStrategy {
+        self.strategy
+    }
+
+    pub fn differential_weight(&self) -> f32 {
+        self.differential_weight
+    }
+
+    pub fn crossover_rate(&self) -> f32 {
+        self.crossover_rate
+    }
+
+    /// Survivors of the last duel, along with their fitness.
+    pub fn targets(&self) -> &[(Chromosome, f32)] {
+        &self.targets
+    }
+
+    /// Lets each trial from `population` fight its target; remembers the
+    /// winners as new targets.
+    fn select_targets<I>(&mut self, population: &[I])
+    where
+        I: Individual,
+    {
+        if self.targets.len() != population.len() {
+            self.targets = population
+                .iter()
+                .map(|individual| (individual.chromosome().clone(), individual.fitness()))
+                .collect();
+
+            return;
+        }
+
+        for (target, trial) in self.targets.iter_mut().zip(population) {
+            let fitness = trial.fitness();
+
+            if fitness >= target.1 || target.1.is_nan() {
+                *target = (trial.chromosome().clone(), fitness);
+            }
+        }
+    }
+
+    fn trial(&self, rng: &mut dyn RngCore, target: usize, best: usize) -> Chromosome {
+        let len = self.targets.len();
+
+        // Indices of distinct individuals other than the target
+        let mut others = index::sample(rng, len - 1, 3).into_iter().map(|idx| {
+            if idx >= target {
+                idx + 1
+            } else {
+                idx
+            }
+        });
+
+        let mut next = || &self.targets[others.next().unwrap()].0;
+
+        let (base, a, b) = match self.strategy {
+            DeStrategy::Rand1Bin => (next(), next(), next()),
+            DeStrategy::Best1Bin => (&self.targets[best].0, next(), next()),
+        };
+
+        let target = &self.targets[target].0;
+        let forced_gene = rng.gen_range(0..target.len());
+
+        let mut trial: Chromosome = (0..target.len())
+            .map(|gene| {
+                if gene == forced_gene || rng.gen_bool(self.crossover_rate as _) {
+                    base[gene] + self.differential_weight * (a[gene] - b[gene])
+                } else {
+                    target[gene]
+                }
+            })
+            .collect();
+
+        if let Some(bounds) = &self.bounds {
+            bounds.repair(rng, self.repair, &mut trial);
+        }
+
+        trial
+    }
+}
+
+impl<I> Optimizer<I> for DifferentialEvolution
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(
+            population.len() >= 4,
+            "differential evolution needs at least 4 individuals"
+        );
+
+        let stats = Statistics::new(population, self.last_max_fitness);
+
+        self.last_max_fitness = Some(stats.max_fitness());
+        self.select_targets(population);
+
+        let fitnesses: Vec<_> = self.targets.iter().map(|(_, fitness)| *fitness).collect();
+        let best = fittest(&fitnesses, 1)[0];
+
+        let new_population: Vec<_> = (0..self.targets.len())
+            .map(|target| I::create(self.trial(rng, target, best)))
+            .collect();
+
+        let stats = stats.with_offspring(&new_population);
+
+        (new_population, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{population, rng, TestIndividual};
+
+    #[test]
+    fn trials_replace_targets_they_are_at_least_as_fit_as() {
+        let mut rng = rng();
+        let mut de = DifferentialEvolution::new(DeStrategy::Rand1Bin);
+
+        let (trials, _) = de.evolve(&mut rng, &population(&[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(trials.len(), 4);
+
+        let trials = vec![
+            TestIndividual::new(&[10.0]).with_fitness(0.0),
+            TestIndividual::new(&[20.0]).with_fitness(2.0),
+            TestIndividual::new(&[30.0]).with_fitness(5.0),
+            TestIndividual::new(&[40.0]).with_fitness(-1.0),
+        ];
+
+        de.evolve(&mut rng, &trials);
+
+        let targets: Vec<_> = de
+            .targets()
+            .iter()
+            .map(|(chromosome, fitness)| (chromosome[0], *fitness))
+            .collect();
+
+        assert_eq!(targets, [(1.0, 1.0), (20.0, 2.0), (30.0, 5.0), (4.0, 4.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "differential evolution needs at least 4 individuals")]
+    fn rejects_too_small_population() {
+        DifferentialEvolution::new(DeStrategy::Best1Bin)
+            .evolve(&mut rng(), &population(&[1.0, 2.0, 3.0]));
+    }
+}
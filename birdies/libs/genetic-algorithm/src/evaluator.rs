@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use crate::{Chromosome, Gene};
+
+/// Computes fitness of chromosomes - see [`GeneticAlghoritm::run_with_evaluator()`].
+///
+/// Implemented for closures, so `|chromosome: &Chromosome| ...` works.
+///
+/// [`GeneticAlghoritm::run_with_evaluator()`]: crate::GeneticAlghoritm::run_with_evaluator
+pub trait Evaluator<G = f32> {
+    fn evaluate(&mut self, chromosome: &Chromosome<G>) -> f32;
+
+    /// Evaluates many chromosomes at once; worth overriding when that's
+    /// cheaper than going one by one (e.g. when fitness comes from a remote
+    /// service or a GPU).
+    fn evaluate_batch(&mut self, chromosomes: &[&Chromosome<G>]) -> Vec<f32> {
+        chromosomes
+            .iter()
+            .map(|chromosome| self.evaluate(chromosome))
+            .collect()
+    }
+}
+
+impl<G, F> Evaluator<G> for F
+where
+    F: FnMut(&Chromosome<G>) -> f32,
+{
+    fn evaluate(&mut self, chromosome: &Chromosome<G>) -> f32 {
+        self(chromosome)
+    }
+}
+
+/// Remembers fitness of chromosomes it's seen, so that identical ones (e.g.
+/// elites, or children that haven't been changed by crossover or mutation)
+/// are evaluated only once.
+///
+/// Chromosomes are compared through [`Gene::to_f32()`], so e.g. `f64` genes
+/// that differ only past `f32`'s precision count as the same.
+pub struct CachedEvaluator<E> {
+    evaluator: E,
+    cache: HashMap<Vec<u32>, f32>,
+    capacity: Option<usize>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<E> CachedEvaluator<E> {
+    /// By default, the cache is unbounded.
+    pub fn new(evaluator: E) -> Self {
+        Self {
+            evaluator,
+            cache: HashMap::new(),
+            capacity: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Limits the cache to `capacity` chromosomes - once it's full, it's
+    /// cleared and starts over.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        self.capacity = Some(capacity);
+        self
+    }
+
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    pub fn into_evaluator(self) -> E {
+        self.evaluator
+    }
+
+    /// Number of evaluations answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of evaluations passed to the underlying evaluator.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    fn insert(&mut self, key: Vec<u32>, fitness: f32) {
+        if self
+            .capacity
+            .is_some_and(|capacity| self.cache.len() >= capacity)
+        {
+            self.cache.clear();
+        }
+
+        self.cache.insert(key, fitness);
+    }
+}
+
+impl<E, G> Evaluator<G> for CachedEvaluator<E>
+where
+    E: Evaluator<G>,
+    G: Gene,
+{
+    fn evaluate(&mut self, chromosome: &Chromosome<G>) -> f32 {
+        let key = key(chromosome);
+
+        if let Some(&fitness) = self.cache.get(&key) {
+            self.hits += 1;
+            return fitness;
+        }
+
+        self.misses += 1;
+
+        let fitness = self.evaluator.evaluate(chromosome);
+
+        self.insert(key, fitness);
+
+        fitness
+    }
+
+    fn evaluate_batch(&mut self, chromosomes: &[&Chromosome<G>]) -> Vec<f32> {
+        let keys: Vec<_> = chromosomes
+            .iter()
+            .map(|chromosome| key(chromosome))
+            .collect();
+
+        // Chromosomes missing from the cache, each one only once
+        let mut missing: HashMap<&[u32], usize> = HashMap::new();
+        let mut pending = Vec::new();
+
+        for (idx, key) in keys.iter().enumerate() {
+            if self.cache.contains_key(key) || missing.contains_key(key.as_slice()) {
+                self.hits += 1;
+            } else {
+                missing.insert(key, pending.len());
+                pending.push(chromosomes[idx]);
+            }
+        }
+
+        self.misses += pending.len();
+
+        let fitnesses = self.evaluator.evaluate_batch(&pending);
+
+        assert_eq!(
+            fitnesses.len(),
+            pending.len(),
+            "evaluator should return one fitness per chromosome"
+        );
+
+        let results = keys
+            .iter()
+            .map(|key| match missing.get(key.as_slice()) {
+                Some(&idx) => fitnesses[idx],
+                None => self.cache[key],
+            })
+            .collect();
+
+        for (key, idx) in missing {
+            self.insert(key.to_vec(), fitnesses[idx]);
+        }
+
+        results
+    }
+}
+
+fn key<G>(chromosome: &Chromosome<G>) -> Vec<u32>
+where
+    G: Gene,
+{
+    chromosome
+        .iter()
+        .map(|gene| gene.to_f32().to_bits())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums genes, remembering each batch it's been given.
+    #[derive(Default)]
+    struct Recorder {
+        batches: Vec<Vec<f32>>,
+    }
+
+    impl Evaluator for Recorder {
+        fn evaluate(&mut self, chromosome: &Chromosome) -> f32 {
+            chromosome.iter().sum()
+        }
+
+        fn evaluate_batch(&mut self, chromosomes: &[&Chromosome]) -> Vec<f32> {
+            self.batches
+                .push(chromosomes.iter().map(|chromosome| chromosome[0]).collect());
+
+            chromosomes
+                .iter()
+                .map(|chromosome| self.evaluate(chromosome))
+                .collect()
+        }
+    }
+
+    fn chromosome(gene: f32) -> Chromosome {
+        [gene, 1.0].iter().copied().collect()
+    }
+
+    #[test]
+    fn batch_evaluates_each_missing_chromosome_once() {
+        let (a, b, c) = (chromosome(1.0), chromosome(2.0), chromosome(3.0));
+        let mut evaluator = CachedEvaluator::new(Recorder::default());
+
+        assert_eq!(
+            evaluator.evaluate_batch(&[&a, &b, &a, &a]),
+            [2.0, 3.0, 2.0, 2.0]
+        );
+        assert_eq!((evaluator.hits(), evaluator.misses()), (2, 2));
+
+        assert_eq!(evaluator.evaluate_batch(&[&b, &c]), [3.0, 4.0]);
+        assert_eq!((evaluator.hits(), evaluator.misses()), (3, 3));
+
+        assert_eq!(evaluator.evaluator().batches, [vec![1.0, 2.0], vec![3.0]]);
+        assert_eq!(evaluator.len(), 3);
+    }
+
+    #[test]
+    fn full_cache_is_cleared() {
+        let (a, b, c) = (chromosome(1.0), chromosome(2.0), chromosome(3.0));
+        let mut evaluator = CachedEvaluator::new(Recorder::default()).with_capacity(2);
+
+        evaluator.evaluate(&a);
+        evaluator.evaluate(&b);
+        assert_eq!(evaluator.len(), 2);
+
+        evaluator.evaluate(&c);
+        assert_eq!(evaluator.len(), 1);
+
+        evaluator.evaluate(&a);
+        assert_eq!((evaluator.hits(), evaluator.misses()), (0, 4));
+
+        evaluator.evaluate(&c);
+        assert_eq!((evaluator.hits(), evaluator.misses()), (1, 4));
+    }
+}
@@ -0,0 +1,74 @@
+/// Type that can be stored inside a [`Chromosome`](crate::Chromosome).
+///
+/// Conversions to and from `f32` are used only for reporting, niching and
+/// enforcing [`Bounds`](crate::Bounds) (see e.g.
+/// [`Chromosome::distance()`](crate::Chromosome::distance)) - operators work
+/// on genes directly.
+pub trait Gene: Clone + Send + Sync + 'static {
+    fn to_f32(&self) -> f32;
+    fn from_f32(value: f32) -> Self;
+}
+
+impl Gene for f32 {
+    fn to_f32(&self) -> f32 {
+        *self
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+}
+
+impl Gene for f64 {
+    fn to_f32(&self) -> f32 {
+        *self as f32
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+}
+
+impl Gene for bool {
+    fn to_f32(&self) -> f32 {
+        if *self {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value >= 0.5
+    }
+}
+
+impl Gene for i32 {
+    fn to_f32(&self) -> f32 {
+        *self as f32
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value.round() as i32
+    }
+}
+
+impl Gene for i64 {
+    fn to_f32(&self) -> f32 {
+        *self as f32
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value.round() as i64
+    }
+}
+
+impl Gene for usize {
+    fn to_f32(&self) -> f32 {
+        *self as f32
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value.round() as usize
+    }
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Chromosome, Gene, Individual};
+
+/// Archive of the best distinct chromosomes ever seen, across all
+/// generations.
+///
+/// Chromosomes closer to each other than `min_distance` (see
+/// [`Chromosome::distance()`]) count as duplicates - only the fitter one is
+/// kept. By default, only identical chromosomes are duplicates.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HallOfFame<G = f32> {
+    capacity: usize,
+    min_distance: f32,
+    generation: usize,
+    champions: Vec<Champion<G>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Champion<G = f32> {
+    chromosome: Chromosome<G>,
+    #[serde(with = "crate::non_finite")]
+    fitness: f32,
+    generation: usize,
+}
+
+impl<G> HallOfFame<G>
+where
+    G: Gene,
+{
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            capacity,
+            min_distance: 0.0,
+            generation: 0,
+            champions: Vec::new(),
+        }
+    }
+
+    pub fn with_min_distance(mut self, min_distance: f32) -> Self {
+        assert!(min_distance >= 0.0);
+
+        self.min_distance = min_distance;
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn min_distance(&self) -> f32 {
+        self.min_distance
+    }
+
+    /// Champions, best first.
+    pub fn champions(&self) -> &[Champion<G>] {
+        &self.champions
+    }
+
+    pub fn best(&self) -> Option<&Champion<G>> {
+        self.champions.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.champions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.champions.is_empty()
+    }
+
+    /// Considers all (evaluated) individuals of another generation.
+    pub fn update<I>(&mut self, population: &[I])
+    where
+        I: Individual<G>,
+    {
+        let fitnesses: Vec<_> = population.iter().map(Individual::fitness).collect();
+
+        self.record(population, &fitnesses);
+    }
+
+    /// Same as [`Self::update()`], but with fitness coming from the outside
+    /// (e.g. lowered by a penalty).
+    pub(crate) fn record<I>(&mut self, population: &[I], fitnesses: &[f32])
+    where
+        I: Individual<G>,
+    {
+        for (individual, &fitness) in population.iter().zip(fitnesses) {
+            if individual.is_evaluated() {
+                self.insert(individual.chromosome(), fitness);
+            }
+        }
+
+        self.generation += 1;
+    }
+
+    /// Adds `chromosome` if it's good enough; returns whether it's been
+    /// added.
+    pub fn insert(&mut self, chromosome: &Chromosome<G>, fitness: f32) -> bool {
+        if fitness.is_nan() {
+            return false;
+        }
+
+        if self.champions.len() >= self.capacity
+            && self
+                .champions
+                .last()
+                .is_some_and(|worst| worst.fitness >= fitness)
+        {
+            return false;
+        }
+
+        let min_distance = self.min_distance;
+
+        let is_duplicate =
+            |champion: &Champion<G>| champion.chromosome.distance(chromosome) <= min_distance;
+
+        if self
+            .champions
+            .iter()
+            .any(|champion| is_duplicate(champion) && champion.fitness >= fitness)
+        {
+            return false;
+        }
+
+        self.champions.retain(|champion| !is_duplicate(champion));
+
+        let position = self
+            .champions
+            .iter()
+            .position(|champion| champion.fitness < fitness)
+            .unwrap_or(self.champions.len());
+
+        self.champions.insert(
+            position,
+            Champion {
+                chromosome: chromosome.clone(),
+                fitness,
+                generation: self.generation,
+            },
+        );
+
+        self.champions.truncate(self.capacity);
+
+        true
+    }
+}
+
+impl<G> Champion<G> {
+    pub fn chromosome(&self) -> &Chromosome<G> {
+        &self.chromosome
+    }
+
+    pub fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    /// Generation (counting from zero) the champion has been seen in.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
@@ -0,0 +1,97 @@
+use rand::RngCore;
+
+use crate::{Chromosome, Individual, Statistics};
+
+/// Replaces a fraction of each new generation with fresh, random chromosomes,
+/// to bring back diversity that selection has worn away.
+///
+/// Immigrants take places of the last children, so elites are never
+/// replaced.
+pub struct RandomImmigrants<G = f32> {
+    rate: f32,
+    trigger: ImmigrationTrigger,
+    generator: Box<Generator<G>>,
+}
+
+type Generator<G> = dyn Fn(&mut dyn RngCore) -> Chromosome<G> + Send + Sync;
+
+/// When immigrants arrive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImmigrationTrigger {
+    EveryGeneration,
+
+    /// Only when the population's mean pairwise distance (see
+    /// [`Statistics::pairwise_distance()`]) falls below given threshold.
+    DiversityBelow(f32),
+}
+
+impl<G> RandomImmigrants<G> {
+    /// `rate` is the fraction of each generation that gets replaced;
+    /// `generator` creates the immigrants' chromosomes.
+    ///
+    /// By default, immigrants arrive every generation.
+    pub fn new(
+        rate: f32,
+        generator: impl Fn(&mut dyn RngCore) -> Chromosome<G> + Send + Sync + 'static,
+    ) -> Self {
+        assert!((0.0..=1.0).contains(&rate));
+
+        Self {
+            rate,
+            trigger: ImmigrationTrigger::EveryGeneration,
+            generator: Box::new(generator),
+        }
+    }
+
+    pub fn with_trigger(mut self, trigger: ImmigrationTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    pub fn trigger(&self) -> ImmigrationTrigger {
+        self.trigger
+    }
+
+    /// Replaces up to `rate * population.len()` individuals from the end of
+    /// `population`, leaving its first `protected` individuals alone;
+    /// `stats` describe the generation `population` has been bred from.
+    ///
+    /// Each immigrant's chromosome goes through `repair` (e.g. to keep it
+    /// within bounds) before the immigrant is created.
+    pub(crate) fn immigrate<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        stats: &Statistics,
+        population: &mut [I],
+        protected: usize,
+        repair: impl Fn(&mut dyn RngCore, &mut Chromosome<G>),
+    ) where
+        I: Individual<G>,
+    {
+        let is_triggered = match self.trigger {
+            ImmigrationTrigger::EveryGeneration => true,
+            ImmigrationTrigger::DiversityBelow(threshold) => stats.pairwise_distance() < threshold,
+        };
+
+        if !is_triggered {
+            return;
+        }
+
+        let count = ((self.rate * population.len() as f32).round() as usize)
+            .min(population.len().saturating_sub(protected));
+
+        let start = population.len() - count;
+
+        for individual in &mut population[start..] {
+            let mut chromosome = (self.generator)(rng);
+
+            repair(rng, &mut chromosome);
+
+            *individual = I::create(chromosome);
+        }
+    }
+}
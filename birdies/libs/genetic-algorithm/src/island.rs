@@ -0,0 +1,224 @@
+use rand::{Rng, RngCore};
+
+use crate::{fittest, Gene, GeneticAlghoritm, Individual, SelectionMethod, Statistics};
+
+/// Several populations (islands), each evolved by its own
+/// [`GeneticAlghoritm`], exchanging their best individuals every now and then.
+///
+/// Since each island can be configured differently (mutation rate, crossover,
+/// elitism etc.), this makes it possible to explore several strategies at
+/// once, while migration keeps good genes flowing between them.
+pub struct IslandModel<S, G = f32> {
+    islands: Vec<GeneticAlghoritm<S, G>>,
+    topology: MigrationTopology,
+    migration_interval: usize,
+    migrant_count: usize,
+    generation: usize,
+}
+
+/// Who sends migrants to whom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrationTopology {
+    /// Island `n` sends migrants to island `n + 1`, the last one sends them
+    /// to the first one.
+    Ring,
+
+    /// Each island sends migrants to every other island.
+    FullyConnected,
+
+    /// Each island sends migrants to another, randomly chosen island.
+    Random,
+}
+
+impl<S, G> IslandModel<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    /// By default, a single individual migrates every ten generations.
+    pub fn new(islands: Vec<GeneticAlghoritm<S, G>>, topology: MigrationTopology) -> Self {
+        assert!(!islands.is_empty());
+
+        Self {
+            islands,
+            topology,
+            migration_interval: 10,
+            migrant_count: 1,
+            generation: 0,
+        }
+    }
+
+    /// Migrates after every `interval` generations.
+    pub fn with_migration_interval(mut self, interval: usize) -> Self {
+        assert!(interval > 0);
+
+        self.migration_interval = interval;
+        self
+    }
+
+    /// Number of the fittest individuals each island sends through each of
+    /// its outgoing links.
+    pub fn with_migrant_count(mut self, count: usize) -> Self {
+        self.migrant_count = count;
+        self
+    }
+
+    pub fn islands(&self) -> &[GeneticAlghoritm<S, G>] {
+        &self.islands
+    }
+
+    pub fn islands_mut(&mut self) -> &mut [GeneticAlghoritm<S, G>] {
+        &mut self.islands
+    }
+
+    pub fn topology(&self) -> MigrationTopology {
+        self.topology
+    }
+
+    pub fn migration_interval(&self) -> usize {
+        self.migration_interval
+    }
+
+    pub fn migrant_count(&self) -> usize {
+        self.migrant_count
+    }
+
+    /// Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Evolves each island's population (`populations[n]` belongs to the
+    /// `n`-th island), then - if it's time - migrates.
+    ///
+    /// Migrants are the fittest individuals of the given (i.e. already
+    /// evaluated) populations; they replace the last offspring of the
+    /// destination island, never its elites. Returns new populations together
+    /// with statistics of each island.
+    pub fn evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+    ) -> (Vec<Vec<I>>, Vec<Statistics>)
+    where
+        I: Individual<G>,
+    {
+        assert_eq!(populations.len(), self.islands.len());
+
+        let (mut new_populations, stats): (Vec<_>, _) = self
+            .islands
+            .iter_mut()
+            .zip(populations)
+            .map(|(island, population)| island.evolve(rng, population))
+            .unzip();
+
+        self.end_generation(rng, populations, &mut new_populations);
+
+        (new_populations, stats)
+    }
+
+    /// Parallel version of [`Self::evolve()`] - each island evolves on
+    /// rayon's thread pool, with its own RNG derived from `rng`.
+    #[cfg(feature = "parallel")]
+    pub fn evolve_par<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+    ) -> (Vec<Vec<I>>, Vec<Statistics>)
+    where
+        I: Individual<G> + Send + Sync,
+        S: Send,
+    {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+        use rayon::prelude::*;
+
+        assert_eq!(populations.len(), self.islands.len());
+
+        let seed = rng.next_u64();
+
+        let (mut new_populations, stats): (Vec<_>, _) = self
+            .islands
+            .par_iter_mut()
+            .zip(populations)
+            .enumerate()
+            .map(|(idx, (island, population))| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+                rng.set_stream(idx as u64);
+                island.evolve(&mut rng, population)
+            })
+            .unzip();
+
+        self.end_generation(rng, populations, &mut new_populations);
+
+        (new_populations, stats)
+    }
+
+    fn end_generation<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+        new_populations: &mut [Vec<I>],
+    ) where
+        I: Individual<G>,
+    {
+        self.generation += 1;
+
+        if self.generation.is_multiple_of(self.migration_interval) {
+            self.migrate(rng, populations, new_populations);
+        }
+    }
+
+    fn migrate<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+        new_populations: &mut [Vec<I>],
+    ) where
+        I: Individual<G>,
+    {
+        let island_count = self.islands.len();
+
+        if island_count < 2 || self.migrant_count == 0 {
+            return;
+        }
+
+        let mut arrivals = vec![Vec::new(); island_count];
+
+        for (from, (island, population)) in self.islands.iter().zip(populations).enumerate() {
+            let migrants = fittest(&island.fitnesses(population), self.migrant_count);
+
+            let destinations: Vec<_> = match self.topology {
+                MigrationTopology::Ring => vec![(from + 1) % island_count],
+
+                MigrationTopology::FullyConnected => {
+                    (0..island_count).filter(|&to| to != from).collect()
+                }
+
+                MigrationTopology::Random => {
+                    let to = rng.gen_range(0..(island_count - 1));
+
+                    vec![if to >= from { to + 1 } else { to }]
+                }
+            };
+
+            for to in destinations {
+                arrivals[to].extend(migrants.iter().map(|&idx| &population[idx]));
+            }
+        }
+
+        for ((island, population), arrivals) in self
+            .islands
+            .iter()
+            .zip(new_populations.iter_mut())
+            .zip(arrivals)
+        {
+            let first_vacancy = island.elite_count().min(population.len());
+
+            for (slot, migrant) in (first_vacancy..population.len()).rev().zip(arrivals) {
+                population[slot] = I::create(migrant.chromosome().clone());
+            }
+        }
+    }
+}
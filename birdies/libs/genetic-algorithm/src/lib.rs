@@ -1,237 +1,804 @@
 #![feature(impl_trait_in_assoc_type)]
-use std::{iter::FromIterator, ops::Index};
-
-use rand::{seq::SliceRandom, Rng, RngCore};
-
-#[derive(Clone, Debug)]
-pub struct Statistics {
-    min_fitness: f32,
-    max_fitness: f32,
-    avg_fitness: f32,
-}
-pub struct GeneticAlghoritm<S> {
+use std::{
+    iter::{self, FromIterator},
+    mem,
+    ops::{Index, IndexMut},
+};
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+pub use self::{
+    benchmark::*, bounds::*, builder::*, cma_es::*, coevolution::*, config::*, crossover::*,
+    differential_evolution::*, evaluator::*, gene::*, hall_of_fame::*, immigration::*, island::*,
+    mating::*, mutation::*, novelty::*, nsga2::*, observer::*, optimizer::*, run::*, selection::*,
+    snapshot::*, speciation::*, statistics::*, steady_state::*,
+};
+
+mod benchmark;
+mod bounds;
+mod builder;
+mod cma_es;
+mod coevolution;
+mod config;
+mod crossover;
+mod differential_evolution;
+mod evaluator;
+mod gene;
+mod hall_of_fame;
+mod immigration;
+mod island;
+mod mating;
+mod mutation;
+mod non_finite;
+mod novelty;
+mod nsga2;
+mod observer;
+mod optimizer;
+mod run;
+mod selection;
+mod snapshot;
+mod speciation;
+mod statistics;
+mod steady_state;
+
+#[cfg(test)]
+mod testing;
+
+pub struct GeneticAlghoritm<S, G = f32> {
     selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>,
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    elite_count: usize,
+    crossover_probability: f32,
+    mating: Mating,
+    population_size: Option<usize>,
+    speciation: Option<Speciation>,
+    bounds: Option<Bounds>,
+    repair: Repair,
+    penalty: Option<Box<dyn Penalty<G>>>,
+    hall_of_fame: Option<HallOfFame<G>>,
+    novelty_search: Option<NoveltySearch>,
+    immigrants: Option<RandomImmigrants<G>>,
+    observers: Vec<Box<dyn Observer<G>>>,
+    generation: usize,
+    last_max_fitness: Option<f32>,
 }
-pub struct RouletteWheelSelection;
-
-pub struct Chromosome {
-    genes: Vec<f32>,
-}
-
-pub struct UniformCrossover;
 
-pub struct GaussianMutation {
-    chance: f32,
-    coeff: f32,
+/// Genes of a single individual - real numbers by default, but any other
+/// [`Gene`] (e.g. `bool` for binary problems, `i32` for integer ones or
+/// `usize` for permutations) works, too.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
 }
 
-pub trait Individual {
-    fn create(chromsome: Chromosome) -> Self;
-    fn chromosome(&self) -> &Chromosome;
+pub trait Individual<G = f32> {
+    fn create(chromsome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
     fn fitness(&self) -> f32;
-}
-
-pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual;
-}
-
-pub trait CrossoverMethod {
-    fn crossover(
-        &self,
-        rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
-}
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
-}
+    /// Whether `fitness()` is meaningful right after `create()` - that's
+    /// not the case for e.g. birds, whose fitness is known only after
+    /// they've lived for a while.
+    fn is_evaluated(&self) -> bool {
+        true
+    }
 
-impl RouletteWheelSelection {
-    pub fn new() -> Self {
-        Self
+    /// Describes what the individual has *done* (e.g. where a bird has
+    /// flown), as opposed to how well it's done it - see [`NoveltySearch`].
+    fn behaviour(&self) -> Option<Vec<f32>> {
+        None
     }
 }
 
-impl<S> GeneticAlghoritm<S>
+impl<S, G> GeneticAlghoritm<S, G>
 where
     S: SelectionMethod,
+    G: Gene,
 {
     pub fn new(
         selection_method: S,
-        crossover_method: impl CrossoverMethod + 'static,
-        mutation_method: impl MutationMethod + 'static,
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
     ) -> Self {
         Self {
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            elite_count: 0,
+            crossover_probability: 1.0,
+            mating: Mating::default(),
+            population_size: None,
+            speciation: None,
+            bounds: None,
+            repair: Repair::default(),
+            penalty: None,
+            hall_of_fame: None,
+            novelty_search: None,
+            immigrants: None,
+            observers: Vec::new(),
+            generation: 0,
+            last_max_fitness: None,
+        }
+    }
+
+    pub fn builder() -> GeneticAlgorithmBuilder<S, G> {
+        GeneticAlgorithmBuilder::new()
+    }
+
+    /// Copies the `elite_count` fittest chromosomes of each generation
+    /// unchanged into the next one, so that the best solution found so far
+    /// can't get lost to crossover and mutation.
+    pub fn with_elitism(mut self, elite_count: usize) -> Self {
+        self.elite_count = elite_count;
+        self
+    }
+
+    /// Crosses parents over with given probability; otherwise, the child
+    /// starts as an unchanged copy of the first parent (and then gets
+    /// mutated as usual).
+    pub fn with_crossover_probability(mut self, probability: f32) -> Self {
+        assert!((0.0..=1.0).contains(&probability));
+
+        self.crossover_probability = probability;
+        self
+    }
+
+    /// Restricts who mates with whom - see [`Mating`].
+    pub fn with_mating(mut self, mating: Mating) -> Self {
+        self.mating = mating;
+        self
+    }
+
+    /// Enables speciation - see [`Speciation`].
+    pub fn with_speciation(mut self, speciation: Speciation) -> Self {
+        self.speciation = Some(speciation);
+        self
+    }
+
+    /// Keeps genes within `bounds`, fixing the ones that go astray after
+    /// crossover or mutation through `repair`.
+    pub fn with_bounds(mut self, bounds: Bounds, repair: Repair) -> Self {
+        self.bounds = Some(bounds);
+        self.repair = repair;
+        self
+    }
+
+    /// Penalizes constraint violations - see [`Penalty`].
+    pub fn with_penalty(mut self, penalty: impl Penalty<G> + 'static) -> Self {
+        self.penalty = Some(Box::new(penalty));
+        self
+    }
+
+    /// Keeps track of the best chromosomes ever seen - see [`HallOfFame`].
+    pub fn with_hall_of_fame(mut self, hall_of_fame: HallOfFame<G>) -> Self {
+        self.hall_of_fame = Some(hall_of_fame);
+        self
+    }
+
+    /// Mixes novelty into fitness used for selection (elites are still chosen
+    /// by fitness alone) - see [`NoveltySearch`].
+    pub fn with_novelty_search(mut self, novelty_search: NoveltySearch) -> Self {
+        self.novelty_search = Some(novelty_search);
+        self
+    }
+
+    /// Brings fresh chromosomes into each generation - see
+    /// [`RandomImmigrants`].
+    pub fn with_random_immigrants(mut self, immigrants: RandomImmigrants<G>) -> Self {
+        self.immigrants = Some(immigrants);
+        self
+    }
+
+    /// Registers an observer - see [`Observer`].
+    pub fn with_observer(mut self, observer: impl Observer<G> + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn elite_count(&self) -> usize {
+        self.elite_count
+    }
+
+    pub fn crossover_probability(&self) -> f32 {
+        self.crossover_probability
+    }
+
+    pub fn mating(&self) -> Mating {
+        self.mating
+    }
+
+    /// Size of populations returned by `evolve()`; when not set, the new
+    /// population is as large as the old one.
+    pub fn population_size(&self) -> Option<usize> {
+        self.population_size
+    }
+
+    pub fn bounds(&self) -> Option<&Bounds> {
+        self.bounds.as_ref()
+    }
+
+    pub fn repair(&self) -> Repair {
+        self.repair
+    }
+
+    /// Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn novelty_search(&self) -> Option<&NoveltySearch> {
+        self.novelty_search.as_ref()
+    }
+
+    pub fn random_immigrants(&self) -> Option<&RandomImmigrants<G>> {
+        self.immigrants.as_ref()
+    }
+
+    /// Best chromosomes of all populations passed to `evolve()` so far.
+    pub fn hall_of_fame(&self) -> Option<&HallOfFame<G>> {
+        self.hall_of_fame.as_ref()
+    }
+
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+    {
+        let generation = self.begin_generation(population);
+        let mut observers = mem::take(&mut self.observers);
+
+        let offspring = generation.slots.iter().map(|&pool| {
+            let pool = &generation.pools[pool];
+            let (parent_a, parent_b) = self.mating.pick(rng, &self.selection_method, pool);
+            let (parent_a, parent_b) = (parent_a.chromosome(), parent_b.chromosome());
+
+            for observer in &mut observers {
+                observer.on_parents_selected(parent_a, parent_b);
+            }
+
+            let child = I::create(self.breed(rng, parent_a, parent_b));
+
+            for observer in &mut observers {
+                observer.on_offspring_created(child.chromosome());
+            }
+
+            child
+        });
+
+        let mut new_population: Vec<_> = generation
+            .elites
+            .iter()
+            .map(|&idx| I::create(population[idx].chromosome().clone()))
+            .chain(offspring)
+            .collect();
+
+        if let Some(immigrants) = &self.immigrants {
+            immigrants.immigrate(
+                rng,
+                &generation.stats,
+                &mut new_population,
+                generation.elites.len(),
+                |rng, chromosome| self.enforce_bounds(rng, chromosome),
+            );
         }
+
+        let stats = generation.stats.with_offspring(&new_population);
+
+        self.end_generation(observers, &stats);
+
+        (new_population, stats)
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    /// Parallel version of [`Self::evolve()`].
+    ///
+    /// Each child gets its own RNG, derived from a seed drawn from `rng` and
+    /// the child's index - so, given the same `rng`, the outcome is the same
+    /// no matter how many threads there are (though it differs from what
+    /// `evolve()` would return).
+    #[cfg(feature = "parallel")]
+    pub fn evolve_par<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
     where
-        I: Individual,
+        I: Individual<G> + Send + Sync,
+        S: Sync,
     {
-        assert!(!population.is_empty());
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+        use rayon::prelude::*;
+
+        let generation = self.begin_generation(population);
+        let seed = rng.next_u64();
+        let this = &*self;
 
-        let new_population = (0..population.len())
-            .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-                let parent_b = self.selection_method.select(rng, population).chromosome();
+        let families: Vec<_> = generation
+            .slots
+            .par_iter()
+            .enumerate()
+            .map(|(idx, &pool)| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+                rng.set_stream(idx as u64);
 
-                self.mutation_method.mutate(rng, &mut child);
+                let pool = &generation.pools[pool];
+                let (parent_a, parent_b) = this.mating.pick(&mut rng, &this.selection_method, pool);
+                let (parent_a, parent_b) = (parent_a.chromosome(), parent_b.chromosome());
+                let child = I::create(this.breed(&mut rng, parent_a, parent_b));
 
-                I::create(child)
+                (parent_a, parent_b, child)
             })
             .collect();
 
-        let stats = Statistics::new(population);
+        // Observers get notified only now, sequentially and in order, so
+        // that they don't have to worry about being called concurrently
+        let mut observers = mem::take(&mut self.observers);
+
+        let offspring = families.into_iter().map(|(parent_a, parent_b, child)| {
+            for observer in &mut observers {
+                observer.on_parents_selected(parent_a, parent_b);
+                observer.on_offspring_created(child.chromosome());
+            }
+
+            child
+        });
+
+        let mut new_population: Vec<_> = generation
+            .elites
+            .iter()
+            .map(|&idx| I::create(population[idx].chromosome().clone()))
+            .chain(offspring)
+            .collect();
+
+        if let Some(immigrants) = &self.immigrants {
+            immigrants.immigrate(
+                rng,
+                &generation.stats,
+                &mut new_population,
+                generation.elites.len(),
+                |rng, chromosome| self.enforce_bounds(rng, chromosome),
+            );
+        }
+
+        let stats = generation.stats.with_offspring(&new_population);
+
+        self.end_generation(observers, &stats);
 
         (new_population, stats)
     }
 
-    pub fn run(&self) {
-        println!("GeneticAlghoritm::run()");
+    /// Computes statistics of `population`, adapts the operators, picks
+    /// elites and plans who's going to mate with whom.
+    fn begin_generation<'a, I>(&mut self, population: &'a [I]) -> Generation<'a, I>
+    where
+        I: Individual<G>,
+    {
+        assert!(!population.is_empty());
+
+        for observer in &mut self.observers {
+            observer.on_generation_start(self.generation);
+        }
+
+        let mut stats = Statistics::new(population, self.last_max_fitness);
+
+        self.last_max_fitness = Some(stats.max_fitness());
+        self.mutation_method.adapt(&stats);
+
+        let fitnesses = self.fitnesses(population);
+
+        if let Some(hall_of_fame) = &mut self.hall_of_fame {
+            hall_of_fame.record(population, &fitnesses);
+        }
+
+        let population_size = self.population_size.unwrap_or(population.len());
+        let mut elites = fittest(&fitnesses, self.elite_count);
+
+        elites.truncate(population_size);
+
+        // Novelty matters only for selection - elites are chosen by fitness
+        // alone, so that the best solution found so far doesn't get lost
+        let fitnesses = match &mut self.novelty_search {
+            Some(novelty_search) => novelty_search.score(population, &fitnesses),
+            None => fitnesses,
+        };
+
+        let offspring_count = population_size - elites.len();
+
+        let (pools, counts) = if let Some(speciation) = &self.speciation {
+            let chromosomes: Vec<_> = population.iter().map(Individual::chromosome).collect();
+            let species = speciation.cluster(&chromosomes);
+            let fitnesses = non_negative(&fitnesses);
+
+            let pools: Vec<Vec<_>> = species
+                .iter()
+                .map(|members| {
+                    members
+                        .iter()
+                        .map(|&idx| Scored {
+                            individual: &population[idx],
+                            fitness: fitnesses[idx] / members.len() as f32,
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let weights: Vec<f32> = pools
+                .iter()
+                .map(|pool| pool.iter().map(|member| member.fitness).sum())
+                .collect();
+
+            let sizes: Vec<_> = species.iter().map(Vec::len).collect();
+            let counts = Speciation::allocate(offspring_count, &weights, &sizes);
+
+            stats = stats.with_species(sizes);
+
+            (pools, counts)
+        } else {
+            let pool = population
+                .iter()
+                .zip(fitnesses)
+                .map(|(individual, fitness)| Scored {
+                    individual,
+                    fitness,
+                })
+                .collect();
+
+            (vec![pool], vec![offspring_count])
+        };
+
+        let slots = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(pool, &count)| iter::repeat_n(pool, count))
+            .collect();
+
+        Generation {
+            stats,
+            elites,
+            pools,
+            slots,
+        }
+    }
+
+    /// Gives observers (taken out for the duration of `evolve()`) back and
+    /// lets them know the generation is over.
+    fn end_generation(&mut self, mut observers: Vec<Box<dyn Observer<G>>>, stats: &Statistics) {
+        for observer in &mut observers {
+            observer.on_generation_end(self.generation, stats);
+        }
+
+        self.observers = observers;
+        self.generation += 1;
+    }
+
+    fn breed(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        let mut child = if rng.gen_bool(self.crossover_probability as _) {
+            self.crossover_method.crossover(rng, parent_a, parent_b)
+        } else {
+            parent_a.clone()
+        };
+
+        self.enforce_bounds(rng, &mut child);
+        self.mutation_method.mutate(rng, &mut child);
+        self.enforce_bounds(rng, &mut child);
+
+        child
+    }
+
+    fn enforce_bounds(&self, rng: &mut dyn RngCore, chromosome: &mut Chromosome<G>) {
+        if let Some(bounds) = &self.bounds {
+            bounds.repair(rng, self.repair, chromosome);
+        }
     }
-}
 
-impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    /// Returns fitness of each individual, lowered by the penalty (if any).
+    pub(crate) fn fitnesses<I>(&self, population: &[I]) -> Vec<f32>
     where
-        I: Individual,
+        I: Individual<G>,
     {
         population
-            .choose_weighted(rng, |individual| individual.fitness())
-            .expect("population is empty")
+            .iter()
+            .map(|individual| match &self.penalty {
+                Some(penalty) => individual.fitness() - penalty.penalty(individual.chromosome()),
+                None => individual.fitness(),
+            })
+            .collect()
+    }
+}
+
+/// Plan of a single `evolve()` call.
+struct Generation<'a, I> {
+    stats: Statistics,
+    elites: Vec<usize>,
+    /// Groups of individuals that can mate with each other, with fitness
+    /// adjusted for selection purposes.
+    pools: Vec<Vec<Scored<'a, I>>>,
+    /// For each child-to-be-born: index of the pool its parents come from.
+    slots: Vec<usize>,
+}
+
+/// Individual with fitness adjusted for selection purposes (e.g. shared
+/// between the members of a species).
+pub(crate) struct Scored<'a, I> {
+    pub(crate) individual: &'a I,
+    pub(crate) fitness: f32,
+}
+
+impl<I, G> Individual<G> for Scored<'_, I>
+where
+    I: Individual<G>,
+{
+    fn create(_: Chromosome<G>) -> Self {
+        unreachable!("selection methods don't create individuals")
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        self.individual.chromosome()
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn behaviour(&self) -> Option<Vec<f32>> {
+        self.individual.behaviour()
     }
 }
 
-impl Chromosome {
+/// Returns indices of the `count` fittest individuals, best first (NaNs count
+/// as the worst).
+fn fittest(fitnesses: &[f32], count: usize) -> Vec<usize> {
+    let mut indices: Vec<_> = (0..fitnesses.len()).collect();
+
+    indices.sort_by(|&a, &b| {
+        let fitness = |idx: usize| {
+            let fitness = fitnesses[idx];
+
+            if fitness.is_nan() {
+                f32::NEG_INFINITY
+            } else {
+                fitness
+            }
+        };
+
+        fitness(b).total_cmp(&fitness(a))
+    });
+
+    indices.truncate(count);
+    indices
+}
+
+/// Shifts fitnesses so that the worst one sits at zero (NaNs count as the
+/// worst).
+fn non_negative(fitnesses: &[f32]) -> Vec<f32> {
+    let min = fitnesses
+        .iter()
+        .copied()
+        .filter(|fitness| fitness.is_finite())
+        .fold(0.0, f32::min);
+
+    fitnesses
+        .iter()
+        .map(|&fitness| {
+            if fitness.is_finite() {
+                fitness - min
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+impl<G> Chromosome<G> {
     pub fn len(&self) -> usize {
         self.genes.len()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    /// Euclidean distance between both chromosomes' genes (see
+    /// [`Gene::to_f32()`]).
+    ///
+    /// When chromosomes differ in length, each gene missing from the shorter
+    /// one counts as differing by 1.
+    pub fn distance(&self, other: &Chromosome<G>) -> f32
+    where
+        G: Gene,
+    {
+        let missing = self.len().abs_diff(other.len()) as f32;
+
+        (self
+            .iter()
+            .zip(other.iter())
+            .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+            .sum::<f32>()
+            + missing)
+            .sqrt()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
 }
 
-impl Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
     }
 }
 
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
+impl<G> IndexMut<usize> for Chromosome<G> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.genes[index]
+    }
+}
+
+impl<G> FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>>(iter: T) -> Self {
         Self {
             genes: iter.into_iter().collect(),
         }
     }
 }
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = impl Iterator<Item = f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = impl Iterator<Item = G>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
     }
 }
 
-impl UniformCrossover {
-    pub fn new() -> Self {
-        Self
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{rng, TestIndividual};
+
+    #[test]
+    fn elitism_keeps_the_best_chromosomes() {
+        let mut rng = rng();
+
+        let mut ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 10.0),
+        )
+        .with_elitism(2);
+
+        let population = vec![
+            TestIndividual::new(&[5.0, 5.0, 5.0]).with_fitness(-15.0),
+            TestIndividual::new(&[0.1, 0.0, -0.1]).with_fitness(-0.2),
+            TestIndividual::new(&[3.0, 3.0, 3.0]).with_fitness(-9.0),
+            TestIndividual::new(&[0.0, 0.5, 0.0]).with_fitness(-0.5),
+            TestIndividual::new(&[4.0, 4.0, 4.0]).with_fitness(-12.0),
+        ];
+
+        let (offspring, _) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(offspring.len(), population.len());
+        assert_eq!(offspring[0].genes(), [0.1, 0.0, -0.1]);
+        assert_eq!(offspring[1].genes(), [0.0, 0.5, 0.0]);
     }
-}
 
-impl CrossoverMethod for UniformCrossover {
-    fn crossover(
-        &self,
-        rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome {
-        assert_eq!(parent_a.len(), parent_b.len());
+    #[test]
+    fn distance_counts_missing_genes() {
+        let short: Chromosome = [1.0, 2.0].iter().copied().collect();
+        let long: Chromosome = [1.0, 2.0, 50.0, -50.0].iter().copied().collect();
+        let other: Chromosome = [4.0, 6.0].iter().copied().collect();
 
-        let parent_a = parent_a.iter();
-        let parent_b = parent_b.iter();
+        assert_eq!(short.distance(&short), 0.0);
+        assert_eq!(short.distance(&other), 5.0);
+        assert_eq!(short.distance(&long), 2.0f32.sqrt());
+        assert_eq!(long.distance(&short), 2.0f32.sqrt());
 
-        parent_a
-            .zip(parent_b)
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
-            .collect()
-    }
-}
+        let mut hall_of_fame = HallOfFame::new(2);
 
-impl GaussianMutation {
-    pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
-        Self { chance, coeff }
+        assert!(hall_of_fame.insert(&short, 1.0));
+        assert!(hall_of_fame.insert(&long, 0.5));
+        assert_eq!(hall_of_fame.len(), 2);
     }
-}
 
-impl MutationMethod for GaussianMutation {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
-        for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+    #[test]
+    fn immigrants_respect_bounds() {
+        let mut rng = rng();
+
+        let mut ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_bounds(Bounds::global(-1.0, 1.0), Repair::Clamp)
+        .with_random_immigrants(RandomImmigrants::new(1.0, |_: &mut dyn RngCore| {
+            iter::repeat_n(100.0, 3).collect()
+        }));
+
+        let population: Vec<_> = (0..5)
+            .map(|_| TestIndividual::new(&[0.0; 3]).with_fitness(0.0))
+            .collect();
 
-            if rng.gen_bool(self.chance as _) {
-                *gene += sign * self.coeff * rng.gen::<f32>();
-            }
+        let (offspring, _) = ga.evolve(&mut rng, &population);
+
+        for child in &offspring {
+            assert_eq!(child.genes(), [1.0; 3]);
         }
     }
-}
-
-impl Statistics {
-    fn new<I>(population: &[I]) -> Self
-    where
-        I: Individual,
-    {
-        assert!(!population.is_empty());
-
-        let mut min_fitness = population[0].fitness();
-        let mut max_fitness = min_fitness;
-        let mut sum_fitness = 0.0;
-
-        for individual in population {
-            let fitness = individual.fitness();
 
-            min_fitness = min_fitness.min(fitness);
-            max_fitness = max_fitness.max(fitness);
-            sum_fitness += fitness;
-        }
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn evolve_par_is_independent_of_thread_count() {
+        let evolve_on = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+
+            pool.install(|| {
+                let mut rng = rng();
+
+                let mut ga = GeneticAlghoritm::new(
+                    RouletteWheelSelection::new(),
+                    UniformCrossover::new(),
+                    GaussianMutation::new(0.5, 0.5),
+                )
+                .with_elitism(1);
+
+                let mut population: Vec<_> = (0..20)
+                    .map(|idx| TestIndividual::new(&[idx as f32, -(idx as f32), 1.0]))
+                    .collect();
+
+                for _ in 0..5 {
+                    for individual in &mut population {
+                        let genes = individual.genes();
+                        individual.fitness = Some(-genes.iter().map(|x| x * x).sum::<f32>());
+                    }
+
+                    population = ga.evolve_par(&mut rng, &population).0;
+                }
+
+                crate::testing::genes(&population)
+            })
+        };
 
-        Self {
-            min_fitness,
-            max_fitness,
-            avg_fitness: sum_fitness / (population.len() as f32),
-        }
+        assert_eq!(evolve_on(1), evolve_on(4));
     }
 
-    pub fn min_fitness(&self) -> f32 {
-        self.min_fitness
-    }
+    #[test]
+    fn elites_are_chosen_by_fitness_despite_novelty() {
+        let mut rng = rng();
+
+        let mut ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 10.0),
+        )
+        .with_elitism(1)
+        .with_novelty_search(NoveltySearch::new(1, 1.0));
+
+        let population: Vec<_> = [(10.0, 0.0), (1.0, 0.0), (1.0, 0.0), (1.0, 100.0)]
+            .iter()
+            .enumerate()
+            .map(|(idx, &(fitness, behaviour))| {
+                let mut individual = TestIndividual::new(&[idx as f32]).with_fitness(fitness);
+                individual.behaviour = Some(vec![behaviour]);
+                individual
+            })
+            .collect();
 
-    pub fn max_fitness(&self) -> f32 {
-        self.max_fitness
-    }
+        let (offspring, _) = ga.evolve(&mut rng, &population);
 
-    pub fn avg_fitness(&self) -> f32 {
-        self.avg_fitness
+        assert_eq!(offspring[0].genes(), [0.0]);
     }
 }
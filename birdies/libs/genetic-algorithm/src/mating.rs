@@ -0,0 +1,124 @@
+use std::ptr;
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{Gene, Individual, SelectionMethod};
+
+/// Who's allowed to mate with whom.
+///
+/// The first parent is always chosen by the selection method alone; these
+/// rules apply to the second one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Mating {
+    /// Both parents are selected independently - so, every now and then, an
+    /// individual mates with itself and produces a clone.
+    #[default]
+    Random,
+
+    /// The second parent is never the same individual as the first one.
+    Distinct,
+
+    /// Out of `candidates` selected individuals, the one most similar to the
+    /// first parent (see [`Chromosome::distance()`]) becomes the second one
+    /// - like with like, which helps to refine niches.
+    ///
+    /// [`Chromosome::distance()`]: crate::Chromosome::distance
+    Assortative { candidates: usize },
+
+    /// Out of `candidates` selected individuals, the one least similar to
+    /// the first parent becomes the second one - which helps to keep the
+    /// population diverse.
+    Disassortative { candidates: usize },
+}
+
+/// How many times selection gets repeated before a distinct second parent
+/// is picked by hand.
+const MAX_ATTEMPTS: usize = 10;
+
+impl Mating {
+    /// Returns the number of candidates for the second parent, if this kind
+    /// of mating has one.
+    pub(crate) fn candidates(&self) -> Option<usize> {
+        match *self {
+            Self::Random | Self::Distinct => None,
+            Self::Assortative { candidates } | Self::Disassortative { candidates } => {
+                Some(candidates)
+            }
+        }
+    }
+
+    pub(crate) fn pick<'a, S, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        selection_method: &S,
+        pool: &'a [I],
+    ) -> (&'a I, &'a I)
+    where
+        S: SelectionMethod,
+        I: Individual<G>,
+        G: Gene,
+    {
+        let parent_a = selection_method.select(rng, pool);
+
+        let parent_b = match *self {
+            Self::Random => selection_method.select(rng, pool),
+            Self::Distinct => select_other(rng, selection_method, pool, parent_a),
+
+            Self::Assortative { candidates } | Self::Disassortative { candidates } => {
+                let distance =
+                    |candidate: &I| parent_a.chromosome().distance(candidate.chromosome());
+
+                let candidates = (0..candidates.max(1))
+                    .map(|_| select_other(rng, selection_method, pool, parent_a));
+
+                if let Self::Assortative { .. } = self {
+                    candidates
+                        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                        .unwrap()
+                } else {
+                    candidates
+                        .max_by(|a, b| distance(a).total_cmp(&distance(b)))
+                        .unwrap()
+                }
+            }
+        };
+
+        (parent_a, parent_b)
+    }
+}
+
+/// Selects an individual other than `parent` - unless `parent` is the only
+/// one there is.
+fn select_other<'a, S, I, G>(
+    rng: &mut dyn RngCore,
+    selection_method: &S,
+    pool: &'a [I],
+    parent: &'a I,
+) -> &'a I
+where
+    S: SelectionMethod,
+    I: Individual<G>,
+{
+    for _ in 0..MAX_ATTEMPTS {
+        let candidate = selection_method.select(rng, pool);
+
+        if !ptr::eq(candidate, parent) {
+            return candidate;
+        }
+    }
+
+    // Selection keeps insisting on the same individual (e.g. because it's
+    // much fitter than everyone else), so let's pick someone at random
+    let others: Vec<_> = pool
+        .iter()
+        .filter(|candidate| !ptr::eq(*candidate, parent))
+        .collect();
+
+    if others.is_empty() {
+        parent
+    } else {
+        others[rng.gen_range(0..others.len())]
+    }
+}
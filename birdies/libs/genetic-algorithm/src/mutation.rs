@@ -0,0 +1,602 @@
+use std::collections::VecDeque;
+
+use rand::{Rng, RngCore};
+use rand_distr::{Distribution, StandardNormal};
+use serde::{Deserialize, Serialize};
+
+use crate::{crossover::segment, Chromosome, Gene, Statistics};
+
+pub trait MutationMethod<G = f32>: Send + Sync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    /// Called once per generation, before any offspring is mutated; allows
+    /// for adjusting the mutation's parameters as the evolution goes.
+    fn adapt(&mut self, _statistics: &Statistics) {}
+
+    /// Returns parameters changed by `adapt()` so far, so that they can be
+    /// checkpointed (see [`Snapshot`](crate::Snapshot)).
+    fn state(&self) -> MutationState {
+        MutationState::Stateless
+    }
+
+    /// Brings back parameters returned by `state()`; state of a different
+    /// kind of mutation is ignored.
+    fn restore(&mut self, _state: &MutationState) {}
+}
+
+/// Parameters a mutation has adjusted through `adapt()`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MutationState {
+    #[default]
+    Stateless,
+    OneFifthRule {
+        sigma: f32,
+        successes: Vec<bool>,
+        #[serde(with = "crate::non_finite")]
+        last_max_fitness: Option<f32>,
+    },
+    Decaying {
+        sigma: f32,
+    },
+}
+
+/// Nudges genes by `±coeff * uniform(0, 1)`.
+///
+/// Despite the name, the perturbation is *not* normally distributed - see
+/// [`NormalMutation`] for that; this one's kept around, because the
+/// simulation has been tuned for it.
+pub struct GaussianMutation {
+    chance: f32,
+    coeff: f32,
+}
+
+/// Adds `N(0, sigma)` to each gene with probability `chance`.
+#[derive(Clone, Debug)]
+pub struct NormalMutation {
+    chance: f32,
+    sigma: f32,
+}
+
+/// [`NormalMutation`] whose `sigma` follows Rechenberg's 1/5th success rule.
+///
+/// A generation counts as successful when its best fitness beats the
+/// previous generation's best; after each generation, the success rate over
+/// the last `window` generations is compared against 1/5:
+///
+/// - above = we're probably too careful, so `sigma` grows (`sigma /= factor`),
+/// - below = we're probably overshooting, so `sigma` shrinks (`sigma *= factor`).
+#[derive(Clone, Debug)]
+pub struct OneFifthRuleMutation {
+    chance: f32,
+    sigma: f32,
+    factor: f32,
+    window: usize,
+    min_sigma: f32,
+    max_sigma: f32,
+    successes: VecDeque<bool>,
+    last_max_fitness: Option<f32>,
+}
+
+/// [`NormalMutation`] whose `sigma` gets multiplied by `decay` after each
+/// generation, until it reaches `min_sigma`.
+#[derive(Clone, Debug)]
+pub struct DecayingMutation {
+    chance: f32,
+    sigma: f32,
+    decay: f32,
+    min_sigma: f32,
+}
+
+/// Self-adaptive, log-normal mutation, as known from evolution strategies.
+///
+/// Each gene carries its own step size, evolved together with the genes:
+/// a chromosome of length `2 * n` holds `n` genes followed by `n` step
+/// sizes (see [`SelfAdaptiveMutation::attach_step_sizes()`] and
+/// [`SelfAdaptiveMutation::genes()`]).
+///
+/// Since it depends on this layout, it's not available through
+/// [`GeneticAlgorithmConfig`](crate::GeneticAlgorithmConfig), which can't
+/// know how the chromosomes are built.
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    min_sigma: f32,
+}
+
+/// Negates each (binary) gene with probability `chance`.
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    chance: f32,
+}
+
+/// Creep mutation for integer genes: with probability `chance`, adds a
+/// random value from `<-step, step>` to each gene.
+#[derive(Clone, Debug)]
+pub struct CreepMutation {
+    chance: f32,
+    step: i32,
+}
+
+/// Swap mutation for permutations: with probability `chance`, swaps each
+/// gene with another, randomly chosen one.
+#[derive(Clone, Debug)]
+pub struct SwapMutation {
+    chance: f32,
+}
+
+/// Inversion mutation for permutations: with probability `chance`, reverses
+/// a random segment of the chromosome.
+#[derive(Clone, Debug)]
+pub struct InversionMutation {
+    chance: f32,
+}
+
+/// Grows variable-length chromosomes: with probability `chance`, inserts a
+/// gene created by `generator` at a random position.
+///
+/// Genes after the inserted one shift by a position, so with
+/// [`Bounds::PerGene`](crate::Bounds::PerGene) they're repaired against
+/// their new position's range, while the ones pushed past the last range
+/// become unbounded.
+pub struct InsertionMutation<G = f32> {
+    chance: f32,
+    max_len: Option<usize>,
+    generator: Box<GeneGenerator<G>>,
+}
+
+type GeneGenerator<G> = dyn Fn(&mut dyn RngCore) -> G + Send + Sync;
+
+/// Shrinks variable-length chromosomes: with probability `chance`, removes
+/// a random gene.
+///
+/// Genes after the removed one shift back by a position, so with
+/// [`Bounds::PerGene`](crate::Bounds::PerGene) they're repaired against
+/// their new position's range; ranges past the end of the chromosome are
+/// simply unused.
+#[derive(Clone, Debug)]
+pub struct DeletionMutation {
+    chance: f32,
+    min_len: usize,
+}
+
+/// Applies several mutations one after another - e.g. [`InsertionMutation`]
+/// and [`DeletionMutation`] to change the length, followed by
+/// [`NormalMutation`] to change the values.
+///
+/// State of the inner mutations (see [`MutationMethod::state()`]) isn't
+/// checkpointed.
+#[derive(Default)]
+pub struct ChainedMutation<G = f32> {
+    mutations: Vec<Box<dyn MutationMethod<G>>>,
+}
+
+impl GaussianMutation {
+    pub fn new(chance: f32, coeff: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self { chance, coeff }
+    }
+}
+
+impl MutationMethod for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+
+            if rng.gen_bool(self.chance as _) {
+                *gene += sign * self.coeff * rng.gen::<f32>();
+            }
+        }
+    }
+}
+
+impl NormalMutation {
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(sigma >= 0.0);
+
+        Self { chance, sigma }
+    }
+
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+}
+
+impl MutationMethod for NormalMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        mutate_normal(rng, child, self.chance, self.sigma);
+    }
+}
+
+impl OneFifthRuleMutation {
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(sigma > 0.0);
+
+        Self {
+            chance,
+            sigma,
+            factor: 0.82,
+            window: 10,
+            min_sigma: 1e-6,
+            max_sigma: f32::INFINITY,
+            successes: VecDeque::new(),
+            last_max_fitness: None,
+        }
+    }
+
+    /// Sets the factor `sigma` gets multiplied (or divided) by; defaults to
+    /// 0.82, as suggested by Schwefel.
+    pub fn with_factor(mut self, factor: f32) -> Self {
+        assert!(factor > 0.0 && factor < 1.0);
+
+        self.factor = factor;
+        self
+    }
+
+    /// Sets how many recent generations are taken into account; defaults to
+    /// 10.
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(window > 0);
+
+        self.window = window;
+        self
+    }
+
+    pub fn with_sigma_range(mut self, min_sigma: f32, max_sigma: f32) -> Self {
+        assert!(min_sigma > 0.0 && min_sigma <= max_sigma);
+
+        self.min_sigma = min_sigma;
+        self.max_sigma = max_sigma;
+        self.sigma = self.sigma.clamp(min_sigma, max_sigma);
+        self
+    }
+
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+}
+
+impl MutationMethod for OneFifthRuleMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        mutate_normal(rng, child, self.chance, self.sigma);
+    }
+
+    fn adapt(&mut self, statistics: &Statistics) {
+        let max_fitness = statistics.max_fitness();
+
+        if let Some(last_max_fitness) = self.last_max_fitness.replace(max_fitness) {
+            self.successes.push_back(max_fitness > last_max_fitness);
+
+            if self.successes.len() > self.window {
+                self.successes.pop_front();
+            }
+        }
+
+        if self.successes.len() < self.window {
+            return;
+        }
+
+        let success_rate = self.successes.iter().filter(|&&success| success).count() as f32
+            / self.successes.len() as f32;
+
+        if success_rate > 0.2 {
+            self.sigma /= self.factor;
+        } else if success_rate < 0.2 {
+            self.sigma *= self.factor;
+        }
+
+        self.sigma = self.sigma.clamp(self.min_sigma, self.max_sigma);
+    }
+
+    fn state(&self) -> MutationState {
+        MutationState::OneFifthRule {
+            sigma: self.sigma,
+            successes: self.successes.iter().copied().collect(),
+            last_max_fitness: self.last_max_fitness,
+        }
+    }
+
+    fn restore(&mut self, state: &MutationState) {
+        if let MutationState::OneFifthRule {
+            sigma,
+            successes,
+            last_max_fitness,
+        } = state
+        {
+            self.sigma = *sigma;
+            self.successes = successes.iter().copied().collect();
+            self.last_max_fitness = *last_max_fitness;
+        }
+    }
+}
+
+impl DecayingMutation {
+    pub fn new(chance: f32, sigma: f32, decay: f32, min_sigma: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(sigma >= 0.0);
+        assert!(decay > 0.0 && decay <= 1.0);
+        assert!(min_sigma >= 0.0);
+
+        Self {
+            chance,
+            sigma,
+            decay,
+            min_sigma,
+        }
+    }
+
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+}
+
+impl MutationMethod for DecayingMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        mutate_normal(rng, child, self.chance, self.sigma);
+    }
+
+    fn adapt(&mut self, _statistics: &Statistics) {
+        self.sigma = (self.sigma * self.decay).max(self.min_sigma);
+    }
+
+    fn state(&self) -> MutationState {
+        MutationState::Decaying { sigma: self.sigma }
+    }
+
+    fn restore(&mut self, state: &MutationState) {
+        if let MutationState::Decaying { sigma } = state {
+            self.sigma = *sigma;
+        }
+    }
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new(min_sigma: f32) -> Self {
+        assert!(min_sigma >= 0.0);
+
+        Self { min_sigma }
+    }
+
+    /// Builds a chromosome suitable for this mutation - that is: `genes`
+    /// followed by the same number of `initial_sigma`s.
+    pub fn attach_step_sizes(
+        genes: impl IntoIterator<Item = f32>,
+        initial_sigma: f32,
+    ) -> Chromosome {
+        let genes: Vec<_> = genes.into_iter().collect();
+        let step_sizes = vec![initial_sigma; genes.len()];
+
+        genes.into_iter().chain(step_sizes).collect()
+    }
+
+    /// Returns the actual genes, without step sizes.
+    pub fn genes(chromosome: &Chromosome) -> impl Iterator<Item = &f32> {
+        chromosome.iter().take(chromosome.len() / 2)
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        assert!(
+            child.len().is_multiple_of(2),
+            "chromosome should hold both genes and step sizes"
+        );
+
+        let n = child.len() / 2;
+
+        if n == 0 {
+            return;
+        }
+
+        // Learning rates, as recommended by Schwefel
+        let tau_global = 1.0 / (2.0 * n as f32).sqrt();
+        let tau_local = 1.0 / (2.0 * (n as f32).sqrt()).sqrt();
+        let global: f32 = StandardNormal.sample(rng);
+
+        for idx in 0..n {
+            let local: f32 = StandardNormal.sample(rng);
+            let step_size = child[n + idx] * (tau_global * global + tau_local * local).exp();
+            let step_size = step_size.max(self.min_sigma);
+            let perturbation: f32 = StandardNormal.sample(rng);
+
+            child[n + idx] = step_size;
+            child[idx] += step_size * perturbation;
+        }
+    }
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        }
+    }
+}
+
+impl CreepMutation {
+    pub fn new(chance: f32, step: i32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(step > 0);
+
+        Self { chance, step }
+    }
+}
+
+impl MutationMethod<i32> for CreepMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<i32>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = gene.saturating_add(rng.gen_range(-self.step..=self.step));
+            }
+        }
+    }
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for SwapMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        let len = child.len();
+
+        if len < 2 {
+            return;
+        }
+
+        for idx in 0..len {
+            if rng.gen_bool(self.chance as _) {
+                let other = rng.gen_range(0..len);
+
+                child.genes.swap(idx, other);
+            }
+        }
+    }
+}
+
+impl InversionMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for InversionMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if child.len() < 2 || !rng.gen_bool(self.chance as _) {
+            return;
+        }
+
+        let (start, end) = segment(rng, child.len());
+
+        child.genes[start..end].reverse();
+    }
+}
+
+impl<G> InsertionMutation<G> {
+    pub fn new(
+        chance: f32,
+        generator: impl Fn(&mut dyn RngCore) -> G + Send + Sync + 'static,
+    ) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self {
+            chance,
+            max_len: None,
+            generator: Box::new(generator),
+        }
+    }
+
+    /// Stops growing chromosomes once they've got `max_len` genes.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+}
+
+impl<G> MutationMethod<G> for InsertionMutation<G>
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if self.max_len.is_some_and(|max_len| child.len() >= max_len)
+            || !rng.gen_bool(self.chance as _)
+        {
+            return;
+        }
+
+        let idx = rng.gen_range(0..=child.len());
+        let gene = (self.generator)(rng);
+
+        child.genes.insert(idx, gene);
+    }
+}
+
+impl DeletionMutation {
+    /// By default, chromosomes can shrink down to a single gene.
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance, min_len: 1 }
+    }
+
+    /// Stops shrinking chromosomes once they've got `min_len` genes.
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+}
+
+impl<G> MutationMethod<G> for DeletionMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if child.len() <= self.min_len || !rng.gen_bool(self.chance as _) {
+            return;
+        }
+
+        let idx = rng.gen_range(0..child.len());
+
+        child.genes.remove(idx);
+    }
+}
+
+impl<G> ChainedMutation<G> {
+    pub fn new() -> Self {
+        Self {
+            mutations: Vec::new(),
+        }
+    }
+
+    pub fn then(mut self, mutation: impl MutationMethod<G> + 'static) -> Self {
+        self.mutations.push(Box::new(mutation));
+        self
+    }
+}
+
+impl<G> MutationMethod<G> for ChainedMutation<G> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for mutation in &self.mutations {
+            mutation.mutate(rng, child);
+        }
+    }
+
+    fn adapt(&mut self, statistics: &Statistics) {
+        for mutation in &mut self.mutations {
+            mutation.adapt(statistics);
+        }
+    }
+}
+
+fn mutate_normal(rng: &mut dyn RngCore, child: &mut Chromosome, chance: f32, sigma: f32) {
+    for gene in child.iter_mut() {
+        if rng.gen_bool(chance as _) {
+            let perturbation: f32 = StandardNormal.sample(rng);
+
+            *gene += sigma * perturbation;
+        }
+    }
+}
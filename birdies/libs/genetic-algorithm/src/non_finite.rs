@@ -0,0 +1,90 @@
+//! Serde helpers for floats that might be NaN or infinite (e.g. fitness of an
+//! individual that's gone astray) - JSON has no way to represent these, so
+//! they get written as strings (`"NaN"`, `"inf"` and `"-inf"`).
+//!
+//! Usage: `#[serde(with = "crate::non_finite")]` on a field of type `f32`,
+//! or a `Vec` / `Option` of those.
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Encode,
+    S: Serializer,
+{
+    value.encode().serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Encode,
+    D: Deserializer<'de>,
+{
+    T::decode(T::Encoded::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+pub(crate) trait Encode: Sized {
+    type Encoded: Serialize + DeserializeOwned;
+
+    fn encode(&self) -> Self::Encoded;
+    fn decode(encoded: Self::Encoded) -> Result<Self, String>;
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Float {
+    Finite(f32),
+    NonFinite(String),
+}
+
+impl Encode for f32 {
+    type Encoded = Float;
+
+    fn encode(&self) -> Float {
+        if self.is_finite() {
+            Float::Finite(*self)
+        } else {
+            Float::NonFinite(self.to_string())
+        }
+    }
+
+    fn decode(encoded: Float) -> Result<Self, String> {
+        match encoded {
+            Float::Finite(value) => Ok(value),
+
+            Float::NonFinite(value) => value
+                .parse()
+                .map_err(|_| format!("invalid float: {:?}", value)),
+        }
+    }
+}
+
+impl<T> Encode for Vec<T>
+where
+    T: Encode,
+{
+    type Encoded = Vec<T::Encoded>;
+
+    fn encode(&self) -> Self::Encoded {
+        self.iter().map(Encode::encode).collect()
+    }
+
+    fn decode(encoded: Self::Encoded) -> Result<Self, String> {
+        encoded.into_iter().map(T::decode).collect()
+    }
+}
+
+impl<T> Encode for Option<T>
+where
+    T: Encode,
+{
+    type Encoded = Option<T::Encoded>;
+
+    fn encode(&self) -> Self::Encoded {
+        self.as_ref().map(Encode::encode)
+    }
+
+    fn decode(encoded: Self::Encoded) -> Result<Self, String> {
+        encoded.map(T::decode).transpose()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::Individual;
+
+/// Novelty search: rewards individuals for behaving differently than the
+/// ones seen before, instead of (or in addition to) for being fit.
+///
+/// Useful when fitness is deceptive - i.e. when the easiest way to get
+/// *some* fitness leads into a dead end (like birds spinning in place and
+/// catching whatever food flies by).
+///
+/// Novelty of an individual is the mean distance between its behaviour (see
+/// [`Individual::behaviour()`]) and the behaviours of its `k` nearest
+/// neighbours, taken from the current population and the archive of past
+/// behaviours; what selection sees then is:
+///
+/// ```text
+/// (1 - weight) * fitness + weight * novelty
+/// ```
+///
+/// Note that fitness and novelty aren't normalized, so `weight` has to
+/// account for their scales.
+#[derive(Clone, Debug)]
+pub struct NoveltySearch {
+    k: usize,
+    weight: f32,
+    archive_rate: usize,
+    archive_size: usize,
+    archive: Vec<Vec<f32>>,
+}
+
+impl NoveltySearch {
+    /// By default, the most novel individual of each generation gets
+    /// archived, up to 1000 behaviours (after which the oldest ones are
+    /// forgotten).
+    pub fn new(k: usize, weight: f32) -> Self {
+        assert!(k > 0);
+        assert!((0.0..=1.0).contains(&weight));
+
+        Self {
+            k,
+            weight,
+            archive_rate: 1,
+            archive_size: 1000,
+            archive: Vec::new(),
+        }
+    }
+
+    /// Sets how many of the most novel individuals of each generation get
+    /// archived.
+    pub fn with_archive_rate(mut self, archive_rate: usize) -> Self {
+        self.archive_rate = archive_rate;
+        self
+    }
+
+    /// Sets how many behaviours the archive holds at most.
+    pub fn with_archive_size(mut self, archive_size: usize) -> Self {
+        self.archive_size = archive_size;
+        self
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    pub fn archive(&self) -> &[Vec<f32>] {
+        &self.archive
+    }
+
+    pub(crate) fn restore_archive(&mut self, archive: &[Vec<f32>]) {
+        self.archive = archive.to_vec();
+    }
+
+    /// Returns novelty of each individual; individuals without a behaviour
+    /// get zero.
+    pub fn novelty<I, G>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual<G>,
+    {
+        let behaviours: Vec<_> = population.iter().map(Individual::behaviour).collect();
+
+        self.novelty_of(&behaviours)
+    }
+
+    /// Combines `fitnesses` of `population` with its novelty, then archives
+    /// the most novel behaviours.
+    pub(crate) fn score<I, G>(&mut self, population: &[I], fitnesses: &[f32]) -> Vec<f32>
+    where
+        I: Individual<G>,
+    {
+        let behaviours: Vec<_> = population.iter().map(Individual::behaviour).collect();
+        let novelties = self.novelty_of(&behaviours);
+
+        let mut by_novelty: Vec<_> = (0..population.len())
+            .filter(|&idx| behaviours[idx].is_some())
+            .collect();
+
+        by_novelty.sort_by(|&a, &b| novelties[b].total_cmp(&novelties[a]));
+
+        for idx in by_novelty.into_iter().take(self.archive_rate) {
+            self.archive.extend(behaviours[idx].clone());
+        }
+
+        if self.archive.len() > self.archive_size {
+            self.archive
+                .drain(..(self.archive.len() - self.archive_size));
+        }
+
+        fitnesses
+            .iter()
+            .zip(novelties)
+            .map(|(fitness, novelty)| (1.0 - self.weight) * fitness + self.weight * novelty)
+            .collect()
+    }
+
+    fn novelty_of(&self, behaviours: &[Option<Vec<f32>>]) -> Vec<f32> {
+        behaviours
+            .iter()
+            .enumerate()
+            .map(|(idx, behaviour)| {
+                let behaviour = match behaviour {
+                    Some(behaviour) => behaviour,
+                    None => return 0.0,
+                };
+
+                let neighbours = behaviours
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_idx, _)| *other_idx != idx)
+                    .filter_map(|(_, other)| other.as_ref())
+                    .chain(&self.archive);
+
+                let mut distances: Vec<_> =
+                    neighbours.map(|other| distance(behaviour, other)).collect();
+
+                if distances.is_empty() {
+                    return 0.0;
+                }
+
+                distances.sort_by(f32::total_cmp);
+                distances.truncate(self.k);
+
+                distances.iter().sum::<f32>() / distances.len() as f32
+            })
+            .collect()
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "behaviours should have the same length");
+
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
@@ -0,0 +1,501 @@
+use std::{cmp::Ordering, mem};
+
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Gene, GeneticAlghoritm, Individual, SelectionMethod, Statistics};
+
+/// Individual judged by several objectives at once (e.g. food eaten, energy
+/// spent and distance flown) instead of a single fitness.
+///
+/// All objectives are maximized - to minimize something, negate it.
+pub trait MultiObjectiveIndividual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
+    fn objectives(&self) -> Vec<f32>;
+
+    /// Sums the objectives up into a single number - used only for
+    /// reporting (statistics passed to observers, the hall of fame), never
+    /// for selection; by default, it's the mean of all objectives.
+    fn aggregate_fitness(&self) -> f32 {
+        let objectives = self.objectives();
+
+        objectives.iter().sum::<f32>() / objectives.len() as f32
+    }
+}
+
+/// Makes a multi-objective individual look like a single-objective one, for
+/// reporting purposes.
+struct Aggregated<'a, I>(&'a I);
+
+/// Non-dominated sorting of a population, as done by NSGA-II.
+#[derive(Clone, Debug)]
+pub struct ParetoRanking {
+    fronts: Vec<Vec<usize>>,
+    ranks: Vec<usize>,
+    crowding_distances: Vec<f32>,
+}
+
+/// Individuals that aren't dominated by anyone else in the population.
+#[derive(Clone, Debug)]
+pub struct ParetoFront {
+    indices: Vec<usize>,
+    objectives: Vec<Vec<f32>>,
+}
+
+/// Crowded binary tournament: out of two random individuals, picks the one
+/// with the better (lower) rank, breaking ties by the larger crowding
+/// distance.
+#[derive(Clone, Debug, Default)]
+pub struct Nsga2Selection;
+
+/// Whether `a` is at least as good as `b` in all objectives, and strictly
+/// better in at least one.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert_eq!(a.len(), b.len());
+
+    let mut strictly_better = false;
+
+    for (a, b) in a.iter().zip(b) {
+        if a < b {
+            return false;
+        }
+
+        if a > b {
+            strictly_better = true;
+        }
+    }
+
+    strictly_better
+}
+
+/// Splits population into consecutive Pareto fronts: the first front
+/// contains non-dominated individuals, the second one contains individuals
+/// dominated only by the first front, and so on.
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let len = objectives.len();
+    let mut dominated_by = vec![0; len];
+    let mut dominates_over: Vec<Vec<usize>> = vec![Vec::new(); len];
+    let mut fronts = Vec::new();
+    let mut front = Vec::new();
+
+    for a in 0..len {
+        for b in (a + 1)..len {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominates_over[a].push(b);
+                dominated_by[b] += 1;
+            } else if dominates(&objectives[b], &objectives[a]) {
+                dominates_over[b].push(a);
+                dominated_by[a] += 1;
+            }
+        }
+    }
+
+    for (idx, &count) in dominated_by.iter().enumerate() {
+        if count == 0 {
+            front.push(idx);
+        }
+    }
+
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+
+        for &a in &front {
+            for &b in &dominates_over[a] {
+                dominated_by[b] -= 1;
+
+                if dominated_by[b] == 0 {
+                    next_front.push(b);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next_front;
+    }
+
+    fronts
+}
+
+/// Returns crowding distance of each member of `front` (in the same order);
+/// boundary individuals get an infinite distance.
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    if front.len() <= 2 {
+        return vec![f32::INFINITY; front.len()];
+    }
+
+    let mut distances = vec![0.0; front.len()];
+
+    let objective_count = objectives[front[0]].len();
+
+    let columns = (0..objective_count).map(|objective| {
+        front
+            .iter()
+            .map(|&idx| objectives[idx][objective])
+            .collect::<Vec<_>>()
+    });
+
+    for values in columns {
+        let value = |pos: usize| values[pos];
+        let mut order: Vec<_> = (0..front.len()).collect();
+
+        order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+
+        let first = order[0];
+        let last = order[order.len() - 1];
+        let range = value(last) - value(first);
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        if range <= 0.0 {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            distances[window[1]] += (value(window[2]) - value(window[0])) / range;
+        }
+    }
+
+    distances
+}
+
+/// NSGA-II's environmental selection: keeps the best `size` individuals out
+/// of `population`, front by front, with the last front that doesn't fit as
+/// a whole being truncated by crowding distance.
+///
+/// Meant to be applied on parents and their (already evaluated) offspring
+/// combined, before calling [`GeneticAlghoritm::evolve_pareto()`].
+pub fn environmental_selection<I, G>(population: Vec<I>, size: usize) -> Vec<I>
+where
+    I: MultiObjectiveIndividual<G>,
+{
+    let ranking = ParetoRanking::new(&population);
+    let mut keep = vec![false; population.len()];
+
+    for idx in ranking.order().into_iter().take(size) {
+        keep[idx] = true;
+    }
+
+    population
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(individual, keep)| if keep { Some(individual) } else { None })
+        .collect()
+}
+
+impl ParetoRanking {
+    pub fn new<I, G>(population: &[I]) -> Self
+    where
+        I: MultiObjectiveIndividual<G>,
+    {
+        let objectives: Vec<_> = population
+            .iter()
+            .map(MultiObjectiveIndividual::objectives)
+            .collect();
+
+        Self::from_objectives(&objectives)
+    }
+
+    pub fn from_objectives(objectives: &[Vec<f32>]) -> Self {
+        let fronts = non_dominated_sort(objectives);
+        let mut ranks = vec![0; objectives.len()];
+        let mut crowding_distances = vec![0.0; objectives.len()];
+
+        for (rank, front) in fronts.iter().enumerate() {
+            let distances = crowding_distance(objectives, front);
+
+            for (&idx, distance) in front.iter().zip(distances) {
+                ranks[idx] = rank;
+                crowding_distances[idx] = distance;
+            }
+        }
+
+        Self {
+            fronts,
+            ranks,
+            crowding_distances,
+        }
+    }
+
+    pub fn fronts(&self) -> &[Vec<usize>] {
+        &self.fronts
+    }
+
+    /// Index of the front given individual belongs to (0 = Pareto front).
+    pub fn rank(&self, idx: usize) -> usize {
+        self.ranks[idx]
+    }
+
+    pub fn crowding_distance(&self, idx: usize) -> f32 {
+        self.crowding_distances[idx]
+    }
+
+    /// NSGA-II's crowded-comparison operator; `Ordering::Less` means `a` is
+    /// better than `b`.
+    pub fn compare(&self, a: usize, b: usize) -> Ordering {
+        self.ranks[a]
+            .cmp(&self.ranks[b])
+            .then_with(|| self.crowding_distances[b].total_cmp(&self.crowding_distances[a]))
+    }
+
+    /// Returns all indices, from the best individual to the worst one.
+    pub fn order(&self) -> Vec<usize> {
+        let mut order: Vec<_> = (0..self.ranks.len()).collect();
+
+        order.sort_by(|&a, &b| self.compare(a, b));
+        order
+    }
+}
+
+impl ParetoFront {
+    /// Indices (within the population passed to `evolve_pareto()`) of
+    /// individuals forming the front.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Objectives of individuals forming the front, in the same order as
+    /// `indices()`.
+    pub fn objectives(&self) -> &[Vec<f32>] {
+        &self.objectives
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl Nsga2Selection {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn select<'a, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        ranking: &ParetoRanking,
+    ) -> &'a I {
+        assert!(!population.is_empty());
+
+        let a = rng.gen_range(0..population.len());
+        let b = rng.gen_range(0..population.len());
+
+        if ranking.compare(a, b) == Ordering::Greater {
+            &population[b]
+        } else {
+            &population[a]
+        }
+    }
+}
+
+impl<I, G> Individual<G> for Aggregated<'_, I>
+where
+    I: MultiObjectiveIndividual<G>,
+{
+    fn create(_: Chromosome<G>) -> Self {
+        unreachable!("aggregated individuals are only reported, never created")
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        self.0.chromosome()
+    }
+
+    fn fitness(&self) -> f32 {
+        self.0.aggregate_fitness()
+    }
+}
+
+impl<S, G> GeneticAlghoritm<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    /// Multi-objective counterpart of [`Self::evolve()`], driven by NSGA-II.
+    ///
+    /// Parents are picked through [`Nsga2Selection`] (instead of the
+    /// configured selection method) and elites are the top individuals by
+    /// crowded comparison. Returns the new population together with the
+    /// Pareto front of `population`.
+    ///
+    /// Since there's no single fitness here, `MutationMethod::adapt()` is
+    /// not called; observers and the hall of fame get
+    /// [`MultiObjectiveIndividual::aggregate_fitness()`] instead.
+    pub fn evolve_pareto<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, ParetoFront)
+    where
+        I: MultiObjectiveIndividual<G>,
+    {
+        assert!(!population.is_empty());
+
+        let mut observers = mem::take(&mut self.observers);
+
+        for observer in &mut observers {
+            observer.on_generation_start(self.generation);
+        }
+
+        let aggregated: Vec<_> = population.iter().map(Aggregated).collect();
+        let stats = Statistics::new(&aggregated, self.last_max_fitness);
+
+        self.last_max_fitness = Some(stats.max_fitness());
+
+        if let Some(hall_of_fame) = &mut self.hall_of_fame {
+            let fitnesses: Vec<_> = aggregated.iter().map(Individual::fitness).collect();
+
+            hall_of_fame.record(&aggregated, &fitnesses);
+        }
+
+        let objectives: Vec<_> = population
+            .iter()
+            .map(MultiObjectiveIndividual::objectives)
+            .collect();
+
+        let ranking = ParetoRanking::from_objectives(&objectives);
+        let selection = Nsga2Selection::new();
+        let population_size = self.population_size.unwrap_or(population.len());
+
+        let mut elites = ranking.order();
+
+        elites.truncate(self.elite_count.min(population_size));
+
+        let offspring = (elites.len()..population_size).map(|_| {
+            let parent_a = selection.select(rng, population, &ranking).chromosome();
+            let parent_b = selection.select(rng, population, &ranking).chromosome();
+
+            for observer in &mut observers {
+                observer.on_parents_selected(parent_a, parent_b);
+            }
+
+            let child = I::create(self.breed(rng, parent_a, parent_b));
+
+            for observer in &mut observers {
+                observer.on_offspring_created(child.chromosome());
+            }
+
+            child
+        });
+
+        let new_population = elites
+            .iter()
+            .map(|&idx| I::create(population[idx].chromosome().clone()))
+            .chain(offspring)
+            .collect();
+
+        self.end_generation(observers, &stats);
+
+        let indices = ranking.fronts()[0].clone();
+
+        let front = ParetoFront {
+            objectives: indices.iter().map(|&idx| objectives[idx].clone()).collect(),
+            indices,
+        };
+
+        (new_population, front)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{genes, rng, TestIndividual},
+        GaussianMutation, HallOfFame, HistoryObserver, RouletteWheelSelection, UniformCrossover,
+    };
+
+    #[test]
+    fn evolve_pareto_reports_to_observers_and_hall_of_fame() {
+        let mut rng = rng();
+        let history = HistoryObserver::new();
+
+        let mut ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_hall_of_fame(HallOfFame::new(3))
+        .with_observer(history.clone());
+
+        let mut population: Vec<_> = (0..10)
+            .map(|idx| TestIndividual::new(&[idx as f32, -(idx as f32)]))
+            .collect();
+
+        for _ in 0..4 {
+            population = ga.evolve_pareto(&mut rng, &population).0;
+        }
+
+        assert_eq!(ga.generation(), 4);
+        assert_eq!(history.len(), 4);
+        assert_eq!(ga.hall_of_fame().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn non_dominated_sort_splits_into_fronts() {
+        let objectives = vec![
+            vec![3.0, 1.0],
+            vec![1.0, 3.0],
+            vec![2.0, 2.0],
+            vec![1.0, 1.0],
+            vec![0.0, 0.0],
+            vec![2.0, 0.0],
+        ];
+
+        let mut fronts = non_dominated_sort(&objectives);
+
+        for front in &mut fronts {
+            front.sort_unstable();
+        }
+
+        assert_eq!(fronts, [vec![0, 1, 2], vec![3, 5], vec![4]]);
+    }
+
+    #[test]
+    fn crowding_distance_of_boundaries_is_infinite() {
+        let objectives = vec![
+            vec![0.0, 4.0],
+            vec![1.0, 3.0],
+            vec![3.0, 1.0],
+            vec![4.0, 0.0],
+        ];
+
+        assert_eq!(
+            crowding_distance(&objectives, &[0, 1, 2, 3]),
+            [f32::INFINITY, 1.5, 1.5, f32::INFINITY]
+        );
+
+        assert_eq!(
+            crowding_distance(&objectives, &[1, 2]),
+            [f32::INFINITY, f32::INFINITY]
+        );
+    }
+
+    #[test]
+    fn environmental_selection_truncates_last_front_by_crowding_distance() {
+        let population = vec![
+            TestIndividual::new(&[0.0, 4.0]),
+            TestIndividual::new(&[0.0, -1.0]),
+            TestIndividual::new(&[1.0, 3.0]),
+            TestIndividual::new(&[5.0, 5.0]),
+            TestIndividual::new(&[3.5, 0.5]),
+            TestIndividual::new(&[4.0, 0.0]),
+        ];
+
+        let survivors = environmental_selection(population, 4);
+
+        assert_eq!(
+            genes(&survivors),
+            [
+                vec![0.0, 4.0],
+                vec![1.0, 3.0],
+                vec![5.0, 5.0],
+                vec![4.0, 0.0]
+            ]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use crate::{Chromosome, Statistics};
+
+/// Gets notified about what's going on inside [`GeneticAlghoritm::evolve()`]
+/// (and everything built on top of it, e.g. `run()`) and
+/// [`GeneticAlghoritm::evolve_pareto()`].
+///
+/// Generations are counted from zero, per algorithm.
+///
+/// [`GeneticAlghoritm::evolve()`]: crate::GeneticAlghoritm::evolve
+/// [`GeneticAlghoritm::evolve_pareto()`]: crate::GeneticAlghoritm::evolve_pareto
+pub trait Observer<G = f32>: Send + Sync {
+    fn on_generation_start(&mut self, _generation: usize) {}
+
+    fn on_parents_selected(&mut self, _parent_a: &Chromosome<G>, _parent_b: &Chromosome<G>) {}
+
+    /// Called for each bred child (elites don't count).
+    fn on_offspring_created(&mut self, _child: &Chromosome<G>) {}
+
+    fn on_generation_end(&mut self, _generation: usize, _statistics: &Statistics) {}
+}
+
+/// Writes statistics of each generation as a CSV row.
+///
+/// It's a handle - clones share the same writer, so a clone can be kept
+/// around to check for I/O errors after the original's been registered.
+#[derive(Clone)]
+pub struct CsvObserver<W> {
+    state: Arc<Mutex<CsvState<W>>>,
+}
+
+/// Collects statistics of each generation in memory.
+///
+/// It's a handle - clones share the same history, so a clone can be kept
+/// around to read the history after the original's been registered.
+#[derive(Clone, Debug, Default)]
+pub struct HistoryObserver {
+    history: Arc<Mutex<Vec<Statistics>>>,
+}
+
+struct CsvState<W> {
+    writer: W,
+    has_header: bool,
+    error: Option<io::Error>,
+}
+
+impl<W> CsvObserver<W>
+where
+    W: Write + Send,
+{
+    pub fn new(writer: W) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CsvState {
+                writer,
+                has_header: false,
+                error: None,
+            })),
+        }
+    }
+
+    /// Returns the first I/O error that's happened, if any - after an error,
+    /// nothing else gets written.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.state.lock().unwrap().error.take()
+    }
+}
+
+impl<W> CsvState<W>
+where
+    W: Write,
+{
+    fn write(&mut self, generation: usize, statistics: &Statistics) -> io::Result<()> {
+        if !self.has_header {
+            writeln!(
+                self.writer,
+                "generation,min_fitness,max_fitness,avg_fitness,median_fitness,\
+                 std_dev_fitness,gene_variance,pairwise_distance,species_count"
+            )?;
+
+            self.has_header = true;
+        }
+
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{},{},{},{}",
+            generation,
+            statistics.min_fitness(),
+            statistics.max_fitness(),
+            statistics.avg_fitness(),
+            statistics.median_fitness(),
+            statistics.std_dev_fitness(),
+            statistics.gene_variance(),
+            statistics.pairwise_distance(),
+            statistics.species_count(),
+        )?;
+
+        self.writer.flush()
+    }
+}
+
+impl<G, W> Observer<G> for CsvObserver<W>
+where
+    W: Write + Send,
+{
+    fn on_generation_end(&mut self, generation: usize, statistics: &Statistics) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = state.write(generation, statistics) {
+            state.error = Some(err);
+        }
+    }
+}
+
+impl HistoryObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Statistics of each generation seen so far.
+    pub fn history(&self) -> Vec<Statistics> {
+        self.history.lock().unwrap().clone()
+    }
+
+    pub fn last(&self) -> Option<Statistics> {
+        self.history.lock().unwrap().last().cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.history.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.history.lock().unwrap().is_empty()
+    }
+}
+
+impl<G> Observer<G> for HistoryObserver {
+    fn on_generation_end(&mut self, _generation: usize, statistics: &Statistics) {
+        self.history.lock().unwrap().push(statistics.clone());
+    }
+}
@@ -0,0 +1,35 @@
+use rand::RngCore;
+
+use crate::{Gene, GeneticAlghoritm, HallOfFame, Individual, SelectionMethod, Statistics};
+
+/// Anything that turns one population into the next one - a
+/// [`GeneticAlghoritm`], [`DifferentialEvolution`](crate::DifferentialEvolution)
+/// or [`CmaEs`](crate::CmaEs).
+///
+/// Generic over the individual (instead of `evolve()` being), so that
+/// optimizers can be swapped at runtime through `Box<dyn Optimizer<I>>`.
+pub trait Optimizer<I, G = f32> {
+    /// Returns the next population, which should be evaluated and passed
+    /// back in the next call.
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics);
+
+    /// Best chromosomes seen so far, if the optimizer keeps track of them.
+    fn hall_of_fame(&self) -> Option<&HallOfFame<G>> {
+        None
+    }
+}
+
+impl<S, I, G> Optimizer<I, G> for GeneticAlghoritm<S, G>
+where
+    S: SelectionMethod,
+    I: Individual<G>,
+    G: Gene,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        GeneticAlghoritm::evolve(self, rng, population)
+    }
+
+    fn hall_of_fame(&self) -> Option<&HallOfFame<G>> {
+        GeneticAlghoritm::hall_of_fame(self)
+    }
+}
@@ -0,0 +1,491 @@
+use std::time::{Duration, Instant};
+
+use rand::RngCore;
+
+use crate::{
+    Chromosome, Evaluator, Gene, GeneticAlghoritm, Individual, SelectionMethod, Statistics,
+};
+
+/// Stop conditions for [`GeneticAlghoritm::run()`]; the run ends as soon as
+/// any of the configured conditions is met.
+///
+/// Fitness here is lowered by the algorithm's [`Penalty`](crate::Penalty),
+/// if there's one - so that an individual violating the constraints doesn't
+/// stop the run just because its raw fitness is high.
+#[derive(Clone, Debug, Default)]
+pub struct Termination {
+    max_generations: Option<usize>,
+    target_fitness: Option<f32>,
+    stagnation: Option<usize>,
+    time_budget: Option<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    MaxGenerations,
+    TargetFitness,
+    Stagnation,
+    TimeBudget,
+}
+
+#[derive(Debug)]
+pub struct Outcome<I> {
+    population: Vec<I>,
+    fitnesses: Vec<f32>,
+    best: I,
+    best_fitness: f32,
+    history: Vec<Statistics>,
+    generations: usize,
+    stop_reason: StopReason,
+}
+
+/// Individual paired with fitness coming from the user-supplied evaluator.
+pub(crate) struct Evaluated<I> {
+    pub(crate) individual: I,
+    pub(crate) fitness: f32,
+}
+
+impl Termination {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops after given number of generations has been evolved.
+    pub fn max_generations(mut self, generations: usize) -> Self {
+        self.max_generations = Some(generations);
+        self
+    }
+
+    /// Stops as soon as any individual reaches given fitness.
+    pub fn target_fitness(mut self, fitness: f32) -> Self {
+        self.target_fitness = Some(fitness);
+        self
+    }
+
+    /// Stops when the best fitness hasn't improved for given number of
+    /// generations.
+    pub fn stagnation(mut self, generations: usize) -> Self {
+        self.stagnation = Some(generations);
+        self
+    }
+
+    /// Stops once given amount of wall-clock time has passed; checked
+    /// between generations, so a run can overshoot by one generation.
+    pub fn time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.max_generations.is_none()
+            && self.target_fitness.is_none()
+            && self.stagnation.is_none()
+            && self.time_budget.is_none()
+    }
+}
+
+impl<I> Outcome<I> {
+    /// Final population, evaluated.
+    pub fn population(&self) -> &[I] {
+        &self.population
+    }
+
+    /// Fitness of each individual of the final population (lowered by the
+    /// penalty, if any).
+    pub fn fitnesses(&self) -> &[f32] {
+        &self.fitnesses
+    }
+
+    /// Best individual seen during the entire run (not necessarily present
+    /// in the final population), as judged by the penalized fitness.
+    pub fn best(&self) -> &I {
+        &self.best
+    }
+
+    /// Fitness of [`Self::best()`] (lowered by the penalty, if any).
+    pub fn best_fitness(&self) -> f32 {
+        self.best_fitness
+    }
+
+    /// Statistics of each generation, starting with the initial population
+    /// and ending with the final one.
+    pub fn history(&self) -> &[Statistics] {
+        &self.history
+    }
+
+    /// Number of generations that have been evolved.
+    pub fn generations(&self) -> usize {
+        self.generations
+    }
+
+    pub fn stop_reason(&self) -> StopReason {
+        self.stop_reason
+    }
+
+    pub fn into_population(self) -> Vec<I> {
+        self.population
+    }
+
+    pub fn into_best(self) -> I {
+        self.best
+    }
+}
+
+impl<I, G> Individual<G> for Evaluated<I>
+where
+    I: Individual<G>,
+{
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self {
+            individual: I::create(chromosome),
+            fitness: f32::NAN,
+        }
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        self.individual.chromosome()
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn is_evaluated(&self) -> bool {
+        !self.fitness.is_nan()
+    }
+
+    fn behaviour(&self) -> Option<Vec<f32>> {
+        self.individual.behaviour()
+    }
+}
+
+impl<S, G> GeneticAlghoritm<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    /// Evolves `population` until `termination` says so, using `evaluate` to
+    /// compute fitness of each chromosome (`Individual::fitness()` is not
+    /// used here).
+    pub fn run<I, F>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: Vec<I>,
+        termination: &Termination,
+        mut evaluate: F,
+    ) -> Outcome<I>
+    where
+        I: Individual<G>,
+        F: FnMut(&Chromosome<G>) -> f32,
+    {
+        self.run_with_evaluator(rng, population, termination, &mut evaluate)
+    }
+
+    /// Same as [`Self::run()`], but with fitness coming from an
+    /// [`Evaluator`] - which gets all chromosomes of each generation as a
+    /// single batch.
+    ///
+    /// The evaluator is borrowed, so that its state (e.g. the cache of
+    /// [`CachedEvaluator`](crate::CachedEvaluator)) outlives the run.
+    pub fn run_with_evaluator<I, E>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: Vec<I>,
+        termination: &Termination,
+        evaluator: &mut E,
+    ) -> Outcome<I>
+    where
+        I: Individual<G>,
+        E: Evaluator<G> + ?Sized,
+    {
+        self.run_with(
+            rng,
+            population,
+            termination,
+            |population| {
+                let pending: Vec<_> = (0..population.len())
+                    .filter(|&idx| !population[idx].is_evaluated())
+                    .collect();
+
+                let chromosomes: Vec<_> = pending
+                    .iter()
+                    .map(|&idx| population[idx].chromosome())
+                    .collect();
+
+                let fitnesses = evaluator.evaluate_batch(&chromosomes);
+
+                assert_eq!(
+                    fitnesses.len(),
+                    pending.len(),
+                    "evaluator should return one fitness per chromosome"
+                );
+
+                for (idx, fitness) in pending.into_iter().zip(fitnesses) {
+                    population[idx].fitness = fitness;
+                }
+            },
+            |ga, rng, population| ga.evolve(rng, population),
+        )
+    }
+
+    /// Parallel version of [`Self::run()`] - evaluates fitness and creates
+    /// offspring on rayon's thread pool (see [`Self::evolve_par()`]).
+    #[cfg(feature = "parallel")]
+    pub fn run_par<I, F>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: Vec<I>,
+        termination: &Termination,
+        evaluate: F,
+    ) -> Outcome<I>
+    where
+        I: Individual<G> + Send + Sync,
+        F: Fn(&Chromosome<G>) -> f32 + Sync,
+        S: Sync,
+    {
+        use rayon::prelude::*;
+
+        self.run_with(
+            rng,
+            population,
+            termination,
+            |population| {
+                population
+                    .par_iter_mut()
+                    .filter(|individual| !individual.is_evaluated())
+                    .for_each(|individual| {
+                        individual.fitness = evaluate(individual.chromosome());
+                    });
+            },
+            |ga, rng, population| ga.evolve_par(rng, population),
+        )
+    }
+
+    fn run_with<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: Vec<I>,
+        termination: &Termination,
+        mut evaluate: impl FnMut(&mut [Evaluated<I>]),
+        mut evolve: impl FnMut(
+            &mut Self,
+            &mut dyn RngCore,
+            &[Evaluated<I>],
+        ) -> (Vec<Evaluated<I>>, Statistics),
+    ) -> Outcome<I>
+    where
+        I: Individual<G>,
+    {
+        assert!(!population.is_empty());
+        assert!(
+            !termination.is_empty(),
+            "at least one stop condition is required"
+        );
+
+        let started_at = termination.time_budget.map(|_| Instant::now());
+
+        let mut population: Vec<_> = population
+            .into_iter()
+            .map(|individual| Evaluated {
+                individual,
+                fitness: f32::NAN,
+            })
+            .collect();
+
+        evaluate(&mut population);
+
+        let mut history = Vec::new();
+        let mut best: Option<(Chromosome<G>, f32)> = None;
+        let mut stagnant_for = 0;
+        let mut generation = 0;
+
+        loop {
+            let fitnesses = self.fitnesses(&population);
+
+            let (best_idx, best_fitness) = fitnesses
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|(_, fitness)| !fitness.is_nan())
+                .fold(
+                    None,
+                    |best: Option<(usize, f32)>, (idx, fitness)| match best {
+                        Some((_, best_fitness)) if best_fitness >= fitness => best,
+                        _ => Some((idx, fitness)),
+                    },
+                )
+                .unwrap_or((0, f32::NAN));
+
+            if best
+                .as_ref()
+                .is_none_or(|(_, fitness)| best_fitness > *fitness)
+            {
+                best = Some((population[best_idx].chromosome().clone(), best_fitness));
+                stagnant_for = 0;
+            } else {
+                stagnant_for += 1;
+            }
+
+            let stop_reason = if termination
+                .target_fitness
+                .is_some_and(|target| best_fitness >= target)
+            {
+                Some(StopReason::TargetFitness)
+            } else if termination
+                .max_generations
+                .is_some_and(|max| generation >= max)
+            {
+                Some(StopReason::MaxGenerations)
+            } else if termination
+                .stagnation
+                .is_some_and(|max| stagnant_for >= max)
+            {
+                Some(StopReason::Stagnation)
+            } else if started_at
+                .zip(termination.time_budget)
+                .is_some_and(|(started_at, budget)| started_at.elapsed() >= budget)
+            {
+                Some(StopReason::TimeBudget)
+            } else {
+                None
+            };
+
+            if let Some(stop_reason) = stop_reason {
+                history.push(Statistics::new(&population, self.last_max_fitness));
+
+                // The final population never goes through `evolve()`, so it
+                // has to be recorded here
+                if let Some(hall_of_fame) = &mut self.hall_of_fame {
+                    hall_of_fame.record(&population, &fitnesses);
+                }
+
+                let (best_chromosome, best_fitness) = best.unwrap();
+
+                let population = population
+                    .into_iter()
+                    .map(|individual| individual.individual)
+                    .collect();
+
+                return Outcome {
+                    population,
+                    fitnesses,
+                    best: I::create(best_chromosome),
+                    best_fitness,
+                    history,
+                    generations: generation,
+                    stop_reason,
+                };
+            }
+
+            let (offspring, stats) = evolve(self, rng, &population);
+
+            history.push(stats);
+            population = offspring;
+            generation += 1;
+
+            evaluate(&mut population);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{rng, TestIndividual};
+    use crate::{GaussianMutation, RouletteWheelSelection, UniformCrossover};
+
+    #[test]
+    fn penalized_individual_is_not_the_best() {
+        let mut rng = rng();
+
+        let mut ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 1.0),
+        )
+        .with_penalty(
+            |chromosome: &Chromosome| {
+                if chromosome[0] > 5.0 {
+                    100.0
+                } else {
+                    0.0
+                }
+            },
+        );
+
+        let population = vec![
+            TestIndividual::new(&[1.0]),
+            TestIndividual::new(&[10.0]),
+            TestIndividual::new(&[3.0]),
+        ];
+
+        let termination = Termination::new().target_fitness(5.0).max_generations(0);
+        let outcome = ga.run(&mut rng, population, &termination, |chromosome| {
+            chromosome[0]
+        });
+
+        assert_eq!(outcome.stop_reason(), StopReason::MaxGenerations);
+        assert_eq!(outcome.best().genes(), [3.0]);
+        assert_eq!(outcome.best_fitness(), 3.0);
+        assert_eq!(outcome.fitnesses(), [1.0, -90.0, 3.0]);
+    }
+
+    fn run(termination: &Termination, evaluate: fn(&Chromosome) -> f32) -> Outcome<TestIndividual> {
+        let mut ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 1.0),
+        );
+
+        let population = (0..5)
+            .map(|idx| TestIndividual::new(&[idx as f32, 1.0]))
+            .collect();
+
+        ga.run(&mut rng(), population, termination, evaluate)
+    }
+
+    #[test]
+    fn stops_after_max_generations() {
+        let outcome = run(&Termination::new().max_generations(3), |chromosome| {
+            chromosome[0]
+        });
+
+        assert_eq!(outcome.stop_reason(), StopReason::MaxGenerations);
+        assert_eq!(outcome.generations(), 3);
+        assert_eq!(outcome.history().len(), 4);
+    }
+
+    #[test]
+    fn stops_on_reaching_target_fitness() {
+        let termination = Termination::new().target_fitness(4.0).max_generations(10);
+        let outcome = run(&termination, |chromosome| chromosome[0]);
+
+        assert_eq!(outcome.stop_reason(), StopReason::TargetFitness);
+        assert_eq!(outcome.generations(), 0);
+        assert_eq!(outcome.history().len(), 1);
+        assert_eq!(outcome.best_fitness(), 4.0);
+    }
+
+    #[test]
+    fn stops_on_stagnation() {
+        let termination = Termination::new().stagnation(2).max_generations(10);
+        let outcome = run(&termination, |_| 1.0);
+
+        assert_eq!(outcome.stop_reason(), StopReason::Stagnation);
+        assert_eq!(outcome.generations(), 2);
+        assert_eq!(outcome.history().len(), 3);
+    }
+
+    #[test]
+    fn stops_when_time_budget_runs_out() {
+        let termination = Termination::new()
+            .time_budget(Duration::ZERO)
+            .max_generations(10);
+
+        let outcome = run(&termination, |chromosome| chromosome[0]);
+
+        assert_eq!(outcome.stop_reason(), StopReason::TimeBudget);
+        assert_eq!(outcome.generations(), 0);
+        assert_eq!(outcome.history().len(), 1);
+    }
+}
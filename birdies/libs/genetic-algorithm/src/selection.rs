@@ -0,0 +1,258 @@
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    RngCore,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::Individual;
+
+pub trait SelectionMethod {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>;
+}
+
+/// Fitness-proportionate selection.
+///
+/// Raw fitness goes through the configured [`FitnessScaling`] first; the
+/// resulting weights are then made usable for a roulette wheel:
+///
+/// - NaN and infinite fitnesses count as the worst fitness in the population,
+/// - negative weights are shifted so that the worst individual sits at zero,
+/// - when every weight ends up at zero (e.g. no bird has eaten anything),
+///   the wheel degrades into a uniform pick.
+#[derive(Clone, Debug, Default)]
+pub struct RouletteWheelSelection {
+    scaling: FitnessScaling,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FitnessScaling {
+    /// Use fitness as-is.
+    #[default]
+    None,
+
+    /// Goldberg's linear scaling: `f' = a * f + b`, chosen so that the
+    /// average fitness is preserved and the best individual gets
+    /// `multiplier` times the average (1.2 - 2.0 are the usual picks; it has
+    /// to be greater than 1); applied after shifting fitness to be
+    /// non-negative.
+    Linear { multiplier: f32 },
+
+    /// `f' = max(0, f - (avg - c * stddev))` - individuals more than `c`
+    /// standard deviations below the average are never selected.
+    SigmaTruncation { c: f32 },
+
+    /// `f' = f ^ exponent` (with positive `exponent`), applied after
+    /// shifting fitness to be non-negative.
+    PowerLaw { exponent: f32 },
+}
+
+impl RouletteWheelSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_scaling(scaling: FitnessScaling) -> Self {
+        assert!(
+            scaling.invalid_parameter().is_none(),
+            "invalid fitness scaling: {:?}",
+            scaling
+        );
+
+        Self { scaling }
+    }
+
+    pub fn scaling(&self) -> FitnessScaling {
+        self.scaling
+    }
+
+    /// Returns the (non-negative) weight each individual gets on the wheel.
+    pub fn weights<I, G>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual<G>,
+    {
+        let fitnesses: Vec<_> = population.iter().map(Individual::fitness).collect();
+        let mut weights = self.scaling.scale(&fitnesses);
+
+        shift_to_non_negative(&mut weights);
+
+        weights
+    }
+}
+
+impl SelectionMethod for RouletteWheelSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>,
+    {
+        assert!(!population.is_empty(), "population is empty");
+
+        let weights = self.weights(population);
+        let total: f32 = weights.iter().sum();
+
+        if total > 0.0 && total.is_finite() {
+            let wheel = WeightedIndex::new(&weights).expect("weights should be valid");
+
+            &population[wheel.sample(rng)]
+        } else {
+            population.choose(rng).unwrap()
+        }
+    }
+}
+
+impl FitnessScaling {
+    /// Returns name and value of the parameter that makes this scaling
+    /// invalid (e.g. a power law with negative exponent, which would favour
+    /// the worst individuals), if any.
+    pub fn invalid_parameter(&self) -> Option<(&'static str, f32)> {
+        match *self {
+            Self::None | Self::SigmaTruncation { .. } => None,
+
+            Self::Linear { multiplier } if !(multiplier > 1.0 && multiplier.is_finite()) => {
+                Some(("multiplier", multiplier))
+            }
+
+            Self::PowerLaw { exponent } if !(exponent > 0.0 && exponent.is_finite()) => {
+                Some(("exponent", exponent))
+            }
+
+            Self::Linear { .. } | Self::PowerLaw { .. } => None,
+        }
+    }
+
+    pub fn scale(&self, fitnesses: &[f32]) -> Vec<f32> {
+        let mut fitnesses = fitnesses.to_vec();
+
+        sanitize(&mut fitnesses);
+
+        if fitnesses.is_empty() {
+            return fitnesses;
+        }
+
+        let len = fitnesses.len() as f32;
+        let avg = fitnesses.iter().sum::<f32>() / len;
+
+        match *self {
+            Self::None => fitnesses,
+
+            Self::Linear { multiplier } => {
+                shift_to_non_negative(&mut fitnesses);
+
+                let avg = fitnesses.iter().sum::<f32>() / len;
+                let min = fitnesses.iter().copied().fold(f32::INFINITY, f32::min);
+                let max = fitnesses.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+                if max - avg <= f32::EPSILON {
+                    return vec![1.0; fitnesses.len()];
+                }
+
+                // Preferably stretch so that max' = multiplier * avg; if that
+                // would push the worst individual below zero, scale as much
+                // as possible while keeping min' = 0 instead.
+                let (a, b) = if min > (multiplier * avg - max) / (multiplier - 1.0) {
+                    let delta = max - avg;
+                    let a = (multiplier - 1.0) * avg / delta;
+                    let b = avg * (max - multiplier * avg) / delta;
+
+                    (a, b)
+                } else {
+                    let delta = avg - min;
+                    let a = avg / delta;
+                    let b = -min * avg / delta;
+
+                    (a, b)
+                };
+
+                fitnesses.iter().map(|f| a * f + b).collect()
+            }
+
+            Self::SigmaTruncation { c } => {
+                let variance = fitnesses.iter().map(|f| (f - avg).powi(2)).sum::<f32>() / len;
+                let threshold = avg - c * variance.sqrt();
+
+                fitnesses.iter().map(|f| (f - threshold).max(0.0)).collect()
+            }
+
+            Self::PowerLaw { exponent } => {
+                shift_to_non_negative(&mut fitnesses);
+
+                fitnesses.iter().map(|f| f.powf(exponent)).collect()
+            }
+        }
+    }
+}
+
+/// Replaces non-finite fitnesses with the worst finite one (or zero, if
+/// there's none).
+fn sanitize(fitnesses: &mut [f32]) {
+    let worst = fitnesses
+        .iter()
+        .copied()
+        .filter(|f| f.is_finite())
+        .fold(None, |min: Option<f32>, f| {
+            Some(min.map_or(f, |min| min.min(f)))
+        })
+        .unwrap_or(0.0);
+
+    for fitness in fitnesses.iter_mut() {
+        if !fitness.is_finite() {
+            *fitness = worst;
+        }
+    }
+}
+
+fn shift_to_non_negative(weights: &mut [f32]) {
+    sanitize(weights);
+
+    let min = weights.iter().copied().fold(0.0, f32::min);
+
+    if min < 0.0 {
+        for weight in weights.iter_mut() {
+            *weight -= min;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+    use crate::testing::{population, rng};
+
+    #[test]
+    fn degenerate_fitnesses_fall_back_to_uniform_pick() {
+        let mut rng = rng();
+
+        for fitnesses in [
+            [0.0; 4],
+            [f32::NAN; 4],
+            [f32::NAN, 0.0, f32::INFINITY, f32::NEG_INFINITY],
+        ] {
+            let population = population(&fitnesses);
+            let mut picked = [false; 4];
+
+            for _ in 0..100 {
+                let individual = RouletteWheelSelection::new().select(&mut rng, &population);
+                let idx = population
+                    .iter()
+                    .position(|other| ptr::eq(other, individual))
+                    .unwrap();
+
+                picked[idx] = true;
+            }
+
+            assert_eq!(picked, [true; 4], "fitnesses = {:?}", fitnesses);
+        }
+    }
+
+    #[test]
+    fn negative_fitnesses_keep_their_order() {
+        let weights = RouletteWheelSelection::new().weights(&population(&[-1.0, -3.0, f32::NAN]));
+
+        assert_eq!(weights, [2.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,293 @@
+use std::io::{self, Read, Write};
+
+pub use rand_chacha::ChaCha8Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    Chromosome, Gene, GeneticAlghoritm, HallOfFame, Individual, MutationState, SelectionMethod,
+};
+
+/// Checkpoint of an evolution: a population together with everything that's
+/// needed to continue evolving it exactly as if there was no break.
+///
+/// For the continuation to be bit-exact, the population has to be brought
+/// back through [`Snapshot::population_with()`] and the RNG passed to
+/// `evolve()` must be the [`ChaCha8Rng`] stored here.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot<G = f32> {
+    generation: usize,
+    chromosomes: Vec<Chromosome<G>>,
+    #[serde(with = "crate::non_finite")]
+    fitnesses: Vec<f32>,
+    #[serde(with = "crate::non_finite")]
+    behaviours: Vec<Option<Vec<f32>>>,
+    rng: ChaCha8Rng,
+    #[serde(with = "crate::non_finite")]
+    last_max_fitness: Option<f32>,
+    mutation: MutationState,
+    hall_of_fame: Option<HallOfFame<G>>,
+    #[serde(with = "crate::non_finite")]
+    novelty_archive: Option<Vec<Vec<f32>>>,
+}
+
+impl<G> Snapshot<G> {
+    /// Number of generations the algorithm had evolved when the snapshot
+    /// was taken.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn chromosomes(&self) -> &[Chromosome<G>] {
+        &self.chromosomes
+    }
+
+    /// Fitness of each chromosome, as it was when the snapshot was taken.
+    pub fn fitnesses(&self) -> &[f32] {
+        &self.fitnesses
+    }
+
+    /// Behaviour of each chromosome (see [`Individual::behaviour()`]).
+    pub fn behaviours(&self) -> &[Option<Vec<f32>>] {
+        &self.behaviours
+    }
+
+    pub fn rng(&self) -> &ChaCha8Rng {
+        &self.rng
+    }
+
+    pub fn hall_of_fame(&self) -> Option<&HallOfFame<G>> {
+        self.hall_of_fame.as_ref()
+    }
+
+    /// Archive of past behaviours, if novelty search was enabled.
+    pub fn novelty_archive(&self) -> Option<&[Vec<f32>]> {
+        self.novelty_archive.as_deref()
+    }
+
+    /// Recreates the population through `Individual::create()`.
+    ///
+    /// Note that this loses the stored fitnesses and behaviours (the
+    /// individuals are as if they were just born), so evolving such
+    /// population won't continue the original run exactly - that's what
+    /// [`Self::population_with()`] is for.
+    pub fn population<I>(&self) -> Vec<I>
+    where
+        I: Individual<G>,
+        G: Clone,
+    {
+        self.chromosomes.iter().cloned().map(I::create).collect()
+    }
+
+    /// Recreates the population through `create`, which gets each
+    /// chromosome together with its stored fitness and behaviour.
+    pub fn population_with<I>(
+        &self,
+        mut create: impl FnMut(Chromosome<G>, f32, Option<Vec<f32>>) -> I,
+    ) -> Vec<I>
+    where
+        G: Clone,
+    {
+        self.chromosomes
+            .iter()
+            .cloned()
+            .zip(&self.fitnesses)
+            .enumerate()
+            .map(|(idx, (chromosome, &fitness))| {
+                let behaviour = self.behaviours[idx].clone();
+
+                create(chromosome, fitness, behaviour)
+            })
+            .collect()
+    }
+
+    /// Writes the snapshot as JSON.
+    ///
+    /// JSON can't represent NaNs and infinities, so non-finite fitnesses
+    /// (and behaviours) get written as strings - e.g. `"NaN"`.
+    pub fn save(&self, writer: impl Write) -> io::Result<()>
+    where
+        G: Serialize,
+    {
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+
+    /// Reads a snapshot written by [`Self::save()`].
+    pub fn load(reader: impl Read) -> io::Result<Self>
+    where
+        G: DeserializeOwned,
+    {
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+impl<S, G> GeneticAlghoritm<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    /// Takes a snapshot of `population`, which is about to be passed to
+    /// `evolve()` together with `rng`.
+    pub fn snapshot<I>(&self, population: &[I], rng: &ChaCha8Rng) -> Snapshot<G>
+    where
+        I: Individual<G>,
+    {
+        Snapshot {
+            generation: self.generation,
+            chromosomes: population
+                .iter()
+                .map(|individual| individual.chromosome().clone())
+                .collect(),
+            fitnesses: population.iter().map(Individual::fitness).collect(),
+            behaviours: population.iter().map(Individual::behaviour).collect(),
+            rng: rng.clone(),
+            last_max_fitness: self.last_max_fitness,
+            mutation: self.mutation_method.state(),
+            hall_of_fame: self.hall_of_fame.clone(),
+            novelty_archive: self
+                .novelty_search
+                .as_ref()
+                .map(|novelty_search| novelty_search.archive().to_vec()),
+        }
+    }
+
+    /// Brings back the algorithm's own state (e.g. parameters of an adaptive
+    /// mutation, the hall of fame or the novelty archive) from `snapshot`;
+    /// the algorithm should be configured the same way as the one the
+    /// snapshot was taken of.
+    pub fn restore(&mut self, snapshot: &Snapshot<G>) {
+        self.generation = snapshot.generation;
+        self.last_max_fitness = snapshot.last_max_fitness;
+        self.mutation_method.restore(&snapshot.mutation);
+
+        if let (Some(hall_of_fame), Some(saved)) = (&mut self.hall_of_fame, &snapshot.hall_of_fame)
+        {
+            *hall_of_fame = saved.clone();
+        }
+
+        if let (Some(novelty_search), Some(archive)) =
+            (&mut self.novelty_search, &snapshot.novelty_archive)
+        {
+            novelty_search.restore_archive(archive);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, RngCore, SeedableRng};
+
+    use super::*;
+    use crate::{
+        testing::{genes, TestIndividual},
+        NoveltySearch, OneFifthRuleMutation, RouletteWheelSelection, UniformCrossover,
+    };
+
+    fn new_ga() -> GeneticAlghoritm<RouletteWheelSelection> {
+        GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            OneFifthRuleMutation::new(0.5, 0.1),
+        )
+        .with_elitism(1)
+        .with_hall_of_fame(HallOfFame::new(5))
+        .with_novelty_search(NoveltySearch::new(3, 0.5))
+    }
+
+    fn evaluate(rng: &mut dyn RngCore, individual: &mut TestIndividual) {
+        individual.fitness = Some(rng.gen_range(0.0..10.0));
+        individual.behaviour = Some(vec![rng.gen(), rng.gen()]);
+    }
+
+    #[test]
+    fn resumed_evolution_is_identical() {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut ga = new_ga();
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| TestIndividual::create((0..8).map(|_| rng.gen_range(-1.0..1.0)).collect()))
+            .collect();
+
+        // Evolve a bit, so that the mutation, the hall of fame and the
+        // novelty archive have some state to remember
+        for generation in 0..4 {
+            if generation > 0 {
+                population = ga.evolve(&mut rng, &population).0;
+            }
+
+            for individual in &mut population {
+                evaluate(&mut rng, individual);
+            }
+        }
+
+        let mut saved = Vec::new();
+
+        ga.snapshot(&population, &rng).save(&mut saved).unwrap();
+
+        let (expected, _) = ga.evolve(&mut rng, &population);
+
+        let expected_ga = ga;
+        let snapshot = Snapshot::load(saved.as_slice()).unwrap();
+        let mut ga = new_ga();
+        let mut rng = snapshot.rng().clone();
+
+        ga.restore(&snapshot);
+
+        let population =
+            snapshot.population_with(|chromosome, fitness, behaviour| TestIndividual {
+                chromosome,
+                fitness: Some(fitness),
+                behaviour,
+            });
+
+        let (actual, _) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(ga.generation(), 4);
+        assert_eq!(genes(&actual), genes(&expected));
+        assert_eq!(
+            ga.novelty_search().unwrap().archive(),
+            expected_ga.novelty_search().unwrap().archive()
+        );
+
+        let fitnesses = |ga: &GeneticAlghoritm<_>| -> Vec<_> {
+            ga.hall_of_fame()
+                .unwrap()
+                .champions()
+                .iter()
+                .map(|champion| (champion.fitness(), champion.generation()))
+                .collect()
+        };
+
+        assert_eq!(fitnesses(&ga), fitnesses(&expected_ga));
+    }
+
+    #[test]
+    fn non_finite_values_survive_round_trip() {
+        let mut ga = new_ga();
+        let rng = ChaCha8Rng::seed_from_u64(42);
+
+        let mut population: Vec<_> = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 1.0]
+            .iter()
+            .map(|&fitness| TestIndividual::new(&[0.0]).with_fitness(fitness))
+            .collect();
+
+        population[0].behaviour = Some(vec![f32::NAN, 0.0]);
+
+        ga.evolve(&mut rng.clone(), &population);
+
+        let mut saved = Vec::new();
+
+        ga.snapshot(&population, &rng).save(&mut saved).unwrap();
+
+        let snapshot: Snapshot = Snapshot::load(saved.as_slice()).unwrap();
+        let bits =
+            |values: &[f32]| -> Vec<_> { values.iter().map(|value| value.to_bits()).collect() };
+
+        assert_eq!(
+            bits(snapshot.fitnesses()),
+            bits(&[f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 1.0])
+        );
+
+        assert!(snapshot.behaviours()[0].as_ref().unwrap()[0].is_nan());
+        assert_eq!(snapshot.generation(), 1);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{Chromosome, Gene};
+
+/// Niching through speciation and explicit fitness sharing.
+///
+/// Individuals get clustered into species by chromosome distance; then each
+/// individual's fitness is divided by the size of its species, and each
+/// species breeds its own share of offspring - so that a single, dominant
+/// strategy can't take over the entire population in a few generations.
+#[derive(Clone, Debug)]
+pub struct Speciation {
+    threshold: f32,
+}
+
+impl Speciation {
+    /// `threshold` is the maximum distance (see [`Chromosome::distance()`])
+    /// between an individual and its species' representative.
+    pub fn new(threshold: f32) -> Self {
+        assert!(threshold > 0.0);
+
+        Self { threshold }
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Groups chromosomes into species, returning indices of each species'
+    /// members.
+    ///
+    /// Each chromosome joins the first species whose representative (its
+    /// first member) lies within the threshold, or founds a new species.
+    pub fn cluster<G>(&self, chromosomes: &[&Chromosome<G>]) -> Vec<Vec<usize>>
+    where
+        G: Gene,
+    {
+        let mut species: Vec<Vec<usize>> = Vec::new();
+
+        for (idx, chromosome) in chromosomes.iter().enumerate() {
+            let species_idx = species
+                .iter()
+                .position(|members| chromosomes[members[0]].distance(chromosome) < self.threshold);
+
+            match species_idx {
+                Some(species_idx) => species[species_idx].push(idx),
+                None => species.push(vec![idx]),
+            }
+        }
+
+        species
+    }
+
+    /// Splits `offspring_count` between species proportionally to `weights`
+    /// (by the largest remainder method); when all weights are zero, splits
+    /// proportionally to species' `sizes` instead.
+    pub(crate) fn allocate(offspring_count: usize, weights: &[f32], sizes: &[usize]) -> Vec<usize> {
+        let total: f32 = weights.iter().sum();
+
+        let weights: Vec<f32> = if total > 0.0 && total.is_finite() {
+            weights.to_vec()
+        } else {
+            sizes.iter().map(|&size| size as f32).collect()
+        };
+
+        let total: f32 = weights.iter().sum();
+
+        let quotas: Vec<f32> = weights
+            .iter()
+            .map(|weight| weight / total * offspring_count as f32)
+            .collect();
+
+        let mut counts: Vec<usize> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+        let mut remaining = offspring_count.saturating_sub(counts.iter().sum());
+
+        let mut by_remainder: Vec<_> = (0..quotas.len()).collect();
+
+        by_remainder.sort_by(|&a, &b| {
+            let remainder = |idx: usize| quotas[idx] - quotas[idx].floor();
+
+            remainder(b).total_cmp(&remainder(a))
+        });
+
+        for idx in by_remainder.into_iter().cycle() {
+            if remaining == 0 {
+                break;
+            }
+
+            counts[idx] += 1;
+            remaining -= 1;
+        }
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cluster_joins_first_species_within_threshold() {
+        let chromosomes: Vec<Chromosome> = [0.0, 0.5, 5.0, 0.9, 5.2, 10.0]
+            .iter()
+            .map(|&gene| [gene].iter().copied().collect())
+            .collect();
+
+        let chromosomes: Vec<_> = chromosomes.iter().collect();
+
+        assert_eq!(
+            Speciation::new(1.0).cluster(&chromosomes),
+            [vec![0, 1, 3], vec![2, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn allocate_gives_leftovers_to_largest_remainders() {
+        assert_eq!(
+            Speciation::allocate(7, &[0.5, 0.3, 0.2], &[1, 1, 1]),
+            [4, 2, 1]
+        );
+        assert_eq!(
+            Speciation::allocate(4, &[1.0, 2.0, 3.0], &[1, 1, 1]),
+            [1, 1, 2]
+        );
+    }
+
+    #[test]
+    fn allocate_falls_back_to_sizes_when_all_weights_are_zero() {
+        assert_eq!(Speciation::allocate(6, &[0.0, 0.0], &[1, 2]), [2, 4]);
+    }
+}
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Chromosome, Gene, Individual};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Statistics {
+    min_fitness: f32,
+    max_fitness: f32,
+    avg_fitness: f32,
+    std_dev_fitness: f32,
+    sorted_fitnesses: Vec<f32>,
+    best_index: usize,
+    max_fitness_regressed: bool,
+    gene_variance: f32,
+    pairwise_distance: f32,
+    species_sizes: Vec<usize>,
+    offspring: Option<Box<Statistics>>,
+}
+
+impl Statistics {
+    pub(crate) fn new<I, G>(population: &[I], last_max_fitness: Option<f32>) -> Self
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        assert!(!population.is_empty());
+
+        let mut min_fitness = population[0].fitness();
+        let mut max_fitness = min_fitness;
+        let mut best_index = 0;
+        let mut sum_fitness = 0.0;
+
+        for (idx, individual) in population.iter().enumerate() {
+            let fitness = individual.fitness();
+
+            min_fitness = min_fitness.min(fitness);
+
+            if fitness > max_fitness || max_fitness.is_nan() {
+                max_fitness = fitness;
+                best_index = idx;
+            }
+
+            sum_fitness += fitness;
+        }
+
+        let len = population.len() as f32;
+        let avg_fitness = sum_fitness / len;
+
+        let std_dev_fitness = (population
+            .iter()
+            .map(|individual| (individual.fitness() - avg_fitness).powi(2))
+            .sum::<f32>()
+            / len)
+            .sqrt();
+
+        let mut sorted_fitnesses: Vec<_> = population.iter().map(Individual::fitness).collect();
+
+        sorted_fitnesses.sort_by(f32::total_cmp);
+
+        let chromosomes: Vec<_> = population.iter().map(Individual::chromosome).collect();
+
+        Self {
+            min_fitness,
+            max_fitness,
+            avg_fitness,
+            std_dev_fitness,
+            sorted_fitnesses,
+            best_index,
+            max_fitness_regressed: last_max_fitness.is_some_and(|last| max_fitness < last),
+            gene_variance: gene_variance(&chromosomes),
+            pairwise_distance: pairwise_distance(&chromosomes),
+            species_sizes: Vec::new(),
+            offspring: None,
+        }
+    }
+
+    pub(crate) fn with_species(mut self, species_sizes: Vec<usize>) -> Self {
+        self.species_sizes = species_sizes;
+        self
+    }
+
+    pub(crate) fn with_offspring<I, G>(mut self, offspring: &[I]) -> Self
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        if !offspring.is_empty() && offspring.iter().all(Individual::is_evaluated) {
+            self.offspring = Some(Box::new(Statistics::new(offspring, None)));
+        }
+
+        self
+    }
+
+    pub fn min_fitness(&self) -> f32 {
+        self.min_fitness
+    }
+
+    pub fn max_fitness(&self) -> f32 {
+        self.max_fitness
+    }
+
+    pub fn avg_fitness(&self) -> f32 {
+        self.avg_fitness
+    }
+
+    pub fn median_fitness(&self) -> f32 {
+        self.percentile(50.0)
+    }
+
+    pub fn std_dev_fitness(&self) -> f32 {
+        self.std_dev_fitness
+    }
+
+    /// Returns the fitness below which `percentile` percent of the population
+    /// falls, interpolating linearly between the closest ranks.
+    pub fn percentile(&self, percentile: f32) -> f32 {
+        assert!((0.0..=100.0).contains(&percentile));
+
+        let rank = percentile / 100.0 * (self.sorted_fitnesses.len() - 1) as f32;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f32;
+
+        self.sorted_fitnesses[lower] * (1.0 - weight) + self.sorted_fitnesses[upper] * weight
+    }
+
+    /// Index (within the population passed to `evolve()`) of the individual
+    /// with the highest fitness.
+    pub fn best_index(&self) -> usize {
+        self.best_index
+    }
+
+    /// Whether this generation's best fitness is lower than the previous
+    /// generation's one.
+    pub fn max_fitness_regressed(&self) -> bool {
+        self.max_fitness_regressed
+    }
+
+    /// Variance of each gene across the population, averaged over all genes;
+    /// approaches zero as the population converges.
+    pub fn gene_variance(&self) -> f32 {
+        self.gene_variance
+    }
+
+    /// Average Euclidean distance between any two chromosomes - estimated
+    /// from a fixed sample of pairs when the population is large, so that
+    /// it doesn't cost quadratic time.
+    pub fn pairwise_distance(&self) -> f32 {
+        self.pairwise_distance
+    }
+
+    /// Number of species the population has been divided into; zero when
+    /// speciation is disabled.
+    pub fn species_count(&self) -> usize {
+        self.species_sizes.len()
+    }
+
+    /// Number of members of each species.
+    pub fn species_sizes(&self) -> &[usize] {
+        &self.species_sizes
+    }
+
+    /// Statistics of the newly created generation - available only when
+    /// its fitness is already known at the time of creation (see
+    /// [`Individual::is_evaluated()`]).
+    pub fn offspring(&self) -> Option<&Statistics> {
+        self.offspring.as_deref()
+    }
+}
+
+fn gene_variance<G>(chromosomes: &[&Chromosome<G>]) -> f32
+where
+    G: Gene,
+{
+    let genes = chromosomes.iter().map(|c| c.len()).min().unwrap_or(0);
+
+    if genes == 0 {
+        return 0.0;
+    }
+
+    let len = chromosomes.len() as f32;
+
+    let total_variance: f32 = (0..genes)
+        .map(|gene| {
+            let mean = chromosomes.iter().map(|c| c[gene].to_f32()).sum::<f32>() / len;
+
+            chromosomes
+                .iter()
+                .map(|c| (c[gene].to_f32() - mean).powi(2))
+                .sum::<f32>()
+                / len
+        })
+        .sum();
+
+    total_variance / genes as f32
+}
+
+/// Number of pairs above which [`pairwise_distance()`] gets sampled.
+const MAX_PAIRS: usize = 2048;
+
+fn pairwise_distance<G>(chromosomes: &[&Chromosome<G>]) -> f32
+where
+    G: Gene,
+{
+    let len = chromosomes.len();
+    let mut sum = 0.0;
+    let mut pairs = 0;
+
+    if len * len.saturating_sub(1) / 2 <= MAX_PAIRS {
+        for (idx, a) in chromosomes.iter().enumerate() {
+            for b in &chromosomes[(idx + 1)..] {
+                sum += a.distance(b);
+                pairs += 1;
+            }
+        }
+    } else {
+        // Pairs each chromosome with the next few ones (wrapping around), so
+        // that every chromosome takes part in the same number of pairs
+        for offset in 1..=(MAX_PAIRS / len).max(1) {
+            for (idx, a) in chromosomes.iter().enumerate() {
+                sum += a.distance(chromosomes[(idx + offset) % len]);
+                pairs += 1;
+            }
+        }
+    }
+
+    if pairs == 0 {
+        0.0
+    } else {
+        sum / pairs as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chromosomes(genes: impl Iterator<Item = f32>) -> Vec<Chromosome> {
+        genes.map(|gene| [gene].iter().copied().collect()).collect()
+    }
+
+    #[test]
+    fn pairwise_distance_of_small_population_is_exact() {
+        let chromosomes = chromosomes([0.0, 1.0, 3.0].iter().copied());
+        let chromosomes: Vec<_> = chromosomes.iter().collect();
+
+        assert_eq!(pairwise_distance(&chromosomes), 2.0);
+    }
+
+    #[test]
+    fn pairwise_distance_of_large_population_is_sampled() {
+        // Half of the pairs have distance 1, half - distance 0
+        let chromosomes = chromosomes((0..1000).map(|idx| (idx % 2) as f32));
+        let chromosomes: Vec<_> = chromosomes.iter().collect();
+
+        assert!((pairwise_distance(&chromosomes) - 0.5).abs() < 0.01);
+    }
+}
@@ -0,0 +1,228 @@
+use std::mem;
+
+use rand::{seq::SliceRandom, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{fittest, Gene, GeneticAlghoritm, Individual, Scored, SelectionMethod};
+
+/// Steady-state flavour of a [`GeneticAlghoritm`]: instead of replacing the
+/// entire population at once, children are born a few at a time, each one
+/// taking place of an individual chosen by [`Replacement`].
+///
+/// Fits worlds where individuals are born and die one by one - e.g. birds
+/// that keep flying while their neighbours get replaced.
+///
+/// Crossover, mutation, bounds, penalty and elitism (elites are never
+/// replaced) work as usual; features that work on whole generations
+/// (speciation, novelty search, hall of fame, adaptive mutation and
+/// observers' generation callbacks) are left to `evolve()`.
+pub struct SteadyState<S, G = f32> {
+    ga: GeneticAlghoritm<S, G>,
+    replacement: Replacement,
+    /// For each position in the population: when its current occupant has
+    /// been born (zero for the initial population).
+    births: Vec<usize>,
+    birth_count: usize,
+}
+
+/// Who makes room for a newborn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Replacement {
+    /// The least fit individual; newborns that haven't been evaluated yet
+    /// (see [`Individual::is_evaluated()`]) are spared, so that they get a
+    /// chance to show what they're worth.
+    #[default]
+    Worst,
+
+    /// The individual that's been in the population the longest.
+    Oldest,
+
+    /// A randomly chosen individual.
+    Random,
+}
+
+impl<S, G> SteadyState<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    pub fn new(ga: GeneticAlghoritm<S, G>, replacement: Replacement) -> Self {
+        Self {
+            ga,
+            replacement,
+            births: Vec::new(),
+            birth_count: 0,
+        }
+    }
+
+    pub fn ga(&self) -> &GeneticAlghoritm<S, G> {
+        &self.ga
+    }
+
+    pub fn ga_mut(&mut self) -> &mut GeneticAlghoritm<S, G> {
+        &mut self.ga
+    }
+
+    pub fn replacement(&self) -> Replacement {
+        self.replacement
+    }
+
+    /// Number of children put into the population so far.
+    pub fn birth_count(&self) -> usize {
+        self.birth_count
+    }
+
+    /// Breeds `count` children and puts them in place of as many
+    /// individuals chosen by [`Replacement`]; returns positions of the
+    /// newborns.
+    ///
+    /// All parents come from the population as it was before the call, so
+    /// children never replace their siblings.
+    pub fn step<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &mut [I],
+        count: usize,
+    ) -> Vec<usize>
+    where
+        I: Individual<G>,
+    {
+        let victims = self.victims(rng, population, count);
+        let children = self.breed(rng, population, victims.len());
+
+        for (&idx, child) in victims.iter().zip(children) {
+            self.replace(population, idx, child);
+        }
+
+        victims
+    }
+
+    /// Breeds `count` children out of `population`, without putting them
+    /// anywhere - for when it's the world that decides who dies, e.g.:
+    ///
+    /// ```ignore
+    /// let child = steady_state.breed(rng, &population, 1).remove(0);
+    ///
+    /// steady_state.replace(&mut population, starved_bird, child);
+    /// ```
+    pub fn breed<I>(&mut self, rng: &mut dyn RngCore, population: &[I], count: usize) -> Vec<I>
+    where
+        I: Individual<G>,
+    {
+        assert!(!population.is_empty());
+
+        let pool: Vec<_> = population
+            .iter()
+            .zip(self.ga.fitnesses(population))
+            .map(|(individual, fitness)| Scored {
+                individual,
+                fitness,
+            })
+            .collect();
+
+        let mut observers = mem::take(&mut self.ga.observers);
+
+        let children = (0..count)
+            .map(|_| {
+                let (parent_a, parent_b) =
+                    self.ga.mating.pick(rng, &self.ga.selection_method, &pool);
+                let (parent_a, parent_b) = (parent_a.chromosome(), parent_b.chromosome());
+
+                for observer in &mut observers {
+                    observer.on_parents_selected(parent_a, parent_b);
+                }
+
+                let child = I::create(self.ga.breed(rng, parent_a, parent_b));
+
+                for observer in &mut observers {
+                    observer.on_offspring_created(child.chromosome());
+                }
+
+                child
+            })
+            .collect();
+
+        self.ga.observers = observers;
+
+        children
+    }
+
+    /// Returns positions of (at most) `count` distinct individuals that
+    /// should make room for newborns, according to [`Replacement`]; elites
+    /// are never chosen.
+    pub fn victims<I>(&self, rng: &mut dyn RngCore, population: &[I], count: usize) -> Vec<usize>
+    where
+        I: Individual<G>,
+    {
+        let fitnesses = self.ga.fitnesses(population);
+        let elites = fittest(&fitnesses, self.ga.elite_count);
+
+        let mut candidates: Vec<_> = match self.replacement {
+            Replacement::Worst => fittest(&fitnesses, fitnesses.len())
+                .into_iter()
+                .rev()
+                .filter(|&idx| population[idx].is_evaluated())
+                .collect(),
+
+            Replacement::Oldest | Replacement::Random => (0..population.len()).collect(),
+        };
+
+        candidates.retain(|idx| !elites.contains(idx));
+
+        match self.replacement {
+            Replacement::Worst => {}
+            Replacement::Oldest => candidates.sort_by_key(|&idx| self.birth(idx)),
+            Replacement::Random => candidates.shuffle(rng),
+        }
+
+        candidates.truncate(count);
+        candidates
+    }
+
+    /// Puts `child` at given position, keeping track of its age.
+    pub fn replace<I>(&mut self, population: &mut [I], idx: usize, child: I) {
+        if self.births.len() < population.len() {
+            self.births.resize(population.len(), 0);
+        }
+
+        self.birth_count += 1;
+        self.births[idx] = self.birth_count;
+
+        population[idx] = child;
+    }
+
+    fn birth(&self, idx: usize) -> usize {
+        self.births.get(idx).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{population, rng},
+        GaussianMutation, RouletteWheelSelection, UniformCrossover,
+    };
+
+    #[test]
+    fn worst_replacement_spares_unevaluated_newborns() {
+        let mut rng = rng();
+
+        let ga = GeneticAlghoritm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let mut steady_state = SteadyState::new(ga, Replacement::Worst);
+        let mut population = population(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+
+        let first = steady_state.step(&mut rng, &mut population, 1);
+        let second = steady_state.step(&mut rng, &mut population, 1);
+
+        assert_eq!(first, [0]);
+        assert_eq!(second, [1]);
+        assert!(!population[0].is_evaluated());
+    }
+}
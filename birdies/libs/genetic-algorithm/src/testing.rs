@@ -0,0 +1,95 @@
+//! Fixtures shared by tests.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Chromosome, Individual, MultiObjectiveIndividual};
+
+/// Individual whose fitness and behaviour come from the outside, like a
+/// bird's; as a multi-objective individual, its genes are its objectives.
+pub(crate) struct TestIndividual {
+    pub(crate) chromosome: Chromosome,
+    pub(crate) fitness: Option<f32>,
+    pub(crate) behaviour: Option<Vec<f32>>,
+}
+
+impl TestIndividual {
+    /// Unevaluated individual.
+    pub(crate) fn new(genes: &[f32]) -> Self {
+        Self {
+            chromosome: genes.iter().copied().collect(),
+            fitness: None,
+            behaviour: None,
+        }
+    }
+
+    pub(crate) fn with_fitness(mut self, fitness: f32) -> Self {
+        self.fitness = Some(fitness);
+        self
+    }
+
+    pub(crate) fn genes(&self) -> &[f32] {
+        &self.chromosome.genes
+    }
+}
+
+impl Individual for TestIndividual {
+    fn create(chromosome: Chromosome) -> Self {
+        Self {
+            chromosome,
+            fitness: None,
+            behaviour: None,
+        }
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness.unwrap_or(0.0)
+    }
+
+    fn is_evaluated(&self) -> bool {
+        self.fitness.is_some()
+    }
+
+    fn behaviour(&self) -> Option<Vec<f32>> {
+        self.behaviour.clone()
+    }
+}
+
+impl MultiObjectiveIndividual for TestIndividual {
+    fn create(chromosome: Chromosome) -> Self {
+        <Self as Individual>::create(chromosome)
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    fn objectives(&self) -> Vec<f32> {
+        self.chromosome.genes.clone()
+    }
+}
+
+/// Individuals with given fitnesses, each having its fitness as the only
+/// gene.
+pub(crate) fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+    fitnesses
+        .iter()
+        .map(|&fitness| TestIndividual::new(&[fitness]).with_fitness(fitness))
+        .collect()
+}
+
+/// Genes of each individual.
+pub(crate) fn genes(population: &[TestIndividual]) -> Vec<Vec<f32>> {
+    population
+        .iter()
+        .map(|individual| individual.genes().to_vec())
+        .collect()
+}
+
+pub(crate) fn rng() -> ChaCha8Rng {
+    ChaCha8Rng::from_seed(Default::default())
+}
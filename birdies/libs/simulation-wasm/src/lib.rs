@@ -34,12 +34,23 @@ impl Simulation {
         )
     }
 
+    pub fn replay_champions(&mut self) {
+        self.sim.replay_champions(&mut self.rng);
+    }
+
+    #[allow(deprecated)]
     pub fn world(&self) -> JsValue {
         let world = World::from(self.sim.world());
         JsValue::from_serde(&world).unwrap()
     }
 }
 
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct World {
     pub animals: Vec<Animal>,
@@ -4,6 +4,7 @@ use lib_neural_network as nn;
 use nalgebra as na;
 use rand::{Rng, RngCore};
 use std::f32::consts::*;
+use std::mem;
 
 use std::f32::consts::FRAC_PI_2;
 
@@ -25,7 +26,7 @@ const SPEED_MAX: f32 = 0.005;
 /// yells "stop flying!", a SPEED_ACCEL of:
 ///
 /// - 0.1 = makes it take 5 steps ("5 seconds") for the bird to actually
-///         slow down to SPEED_MIN,
+///   slow down to SPEED_MIN,
 ///
 /// - 0.5 = makes it take 1 step for the bird to slow down to SPEED_MIN.
 ///
@@ -47,11 +48,14 @@ const FOV_ANGLE: f32 = PI + FRAC_PI_4;
 const CELLS: usize = 9;
 pub struct Simulation {
     world: World,
-    ga: ga::GeneticAlghoritm<ga::RouletteWheelSelection>,
+    optimizer: Box<dyn ga::Optimizer<AnimalIndividual>>,
     age: usize,
+    /// Birds (and their age) put aside while champions are being replayed.
+    paused: Option<(Vec<Animal>, usize)>,
 }
 pub struct AnimalIndividual {
-    fitness: f32,
+    fitness: Option<f32>,
+    behaviour: Option<Vec<f32>>,
     chromosome: ga::Chromosome,
 }
 pub struct World {
@@ -74,6 +78,10 @@ pub struct Animal {
     eye: Eye,
     brain: Brain,
     satiation: usize,
+    /// How far the bird has flown from where it started, ignoring the
+    /// wrapping at the edges of the world - birds spinning in place end up
+    /// with a tiny one, no matter how much food they've eaten.
+    displacement: na::Vector2<f32>,
 }
 pub struct Food {
     position: na::Point2<f32>,
@@ -81,13 +89,49 @@ pub struct Food {
 
 impl Simulation {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
+        Self::random_with_config(rng, &Self::default_config())
+            .expect("default config should be valid")
+    }
+
+    pub fn random_with_config(
+        rng: &mut dyn RngCore,
+        config: &ga::GeneticAlgorithmConfig,
+    ) -> Result<Self, ga::ConfigError> {
+        let ga = config.build()?;
+
+        Ok(Self::random_with_optimizer(rng, ga))
+    }
 
-        let ga = ga::GeneticAlghoritm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(0.01, 0.3),
-            // ---------------------- ^--^ -^-^
+    /// Creates a simulation evolved by any optimizer - e.g.
+    /// [`ga::DifferentialEvolution`] or [`ga::CmaEs`] instead of the genetic
+    /// algorithm.
+    pub fn random_with_optimizer(
+        rng: &mut dyn RngCore,
+        optimizer: impl ga::Optimizer<AnimalIndividual> + 'static,
+    ) -> Self {
+        Self {
+            world: World::random(rng),
+            optimizer: Box::new(optimizer),
+            age: 0,
+            paused: None,
+        }
+    }
+
+    /// Swaps the optimizer; the current birds get evolved by the new one
+    /// once this generation ends.
+    pub fn set_optimizer(&mut self, optimizer: impl ga::Optimizer<AnimalIndividual> + 'static) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+    pub fn default_config() -> ga::GeneticAlgorithmConfig {
+        ga::GeneticAlgorithmConfig {
+            selection: ga::SelectionConfig::RouletteWheel,
+            crossover: ga::CrossoverConfig::Uniform,
+            mutation: ga::MutationConfig::Gaussian {
+                chance: 0.01,
+                coeff: 0.3,
+            },
+            // ------------- ^--^ -^-^
             // | Chosen with a bit of experimentation.
             // |
             // | Higher values can make the simulation more chaotic,
@@ -96,9 +140,18 @@ impl Simulation {
             // | is that higher values might also cause current, good
             // | enough solutions to be discarded.
             // ---
-        );
-
-        Self { world, ga, age: 0 }
+            elite_count: 0,
+            crossover_probability: 1.0,
+            mating: ga::Mating::Random,
+            population_size: None,
+            speciation_threshold: None,
+            bounds: None,
+            repair: ga::Repair::Clamp,
+            hall_of_fame_size: Some(10),
+            hall_of_fame_min_distance: 0.0,
+            novelty_weight: None,
+            novelty_neighbours: 15,
+        }
     }
 
     pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<ga::Statistics> {
@@ -107,10 +160,17 @@ impl Simulation {
         self.process_movements();
         self.age += 1;
 
-        if self.age > GENERATION_LENGTH {
-            Some(self.evolve(rng))
-        } else {
+        if self.age <= GENERATION_LENGTH {
+            None
+        } else if let Some((animals, age)) = self.paused.take() {
+            // Replay's over - the current generation picks up where it left
+            // off
+            self.world.animals = animals;
+            self.age = age;
+
             None
+        } else {
+            Some(self.evolve(rng))
         }
     }
 
@@ -122,6 +182,49 @@ impl Simulation {
         }
     }
 
+    /// Best birds of all generations so far - available when the config
+    /// has `hall_of_fame_size` set (which the default config does) and the
+    /// optimizer keeps track of them.
+    pub fn hall_of_fame(&self) -> Option<&ga::HallOfFame> {
+        self.optimizer.hall_of_fame()
+    }
+
+    /// Puts current birds aside and lets the hall-of-fame ones fly for a
+    /// generation, so that past champions can be watched again; afterwards,
+    /// current birds come back and continue as if nothing happened (the
+    /// replay doesn't affect the evolution).
+    ///
+    /// If there are fewer champions than birds, champions get repeated;
+    /// does nothing when the hall of fame is disabled or still empty.
+    pub fn replay_champions(&mut self, rng: &mut dyn RngCore) {
+        let champions = match self.optimizer.hall_of_fame() {
+            Some(hall_of_fame) if !hall_of_fame.is_empty() => hall_of_fame.champions(),
+            _ => return,
+        };
+
+        let animals = champions
+            .iter()
+            .cycle()
+            .take(self.world.animals.len())
+            .map(|champion| Animal::from_chromosome(champion.chromosome().clone(), rng))
+            .collect();
+
+        let animals = mem::replace(&mut self.world.animals, animals);
+
+        // When replaying already, the birds put aside are still the ones to
+        // come back to
+        if self.paused.is_none() {
+            self.paused = Some((animals, self.age));
+        }
+
+        self.age = 0;
+    }
+
+    /// Whether champions are being replayed at the moment.
+    pub fn is_replaying(&self) -> bool {
+        self.paused.is_some()
+    }
+
     fn evolve(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
         self.age = 0;
 
@@ -134,7 +237,7 @@ impl Simulation {
             .collect();
 
         // Step 2: Evolve birdies
-        let (evolved_population, stats) = self.ga.evolve(rng, &current_population);
+        let (evolved_population, stats) = self.optimizer.evolve(rng, &current_population);
 
         // Step 3: Bring birdies back from the genetic algorithm
         self.world.animals = evolved_population
@@ -207,7 +310,10 @@ impl Simulation {
 
     fn process_movements(&mut self) {
         for animal in &mut self.world.animals {
-            animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
+            let velocity = animal.rotation * na::Vector2::new(0.0, animal.speed);
+
+            animal.position += velocity;
+            animal.displacement += velocity;
 
             animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
             animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
@@ -279,6 +385,7 @@ impl Animal {
             eye,
             brain,
             satiation: 0,
+            displacement: na::Vector2::zeros(),
         }
     }
 
@@ -452,11 +559,26 @@ impl Default for Eye {
 impl AnimalIndividual {
     pub fn from_animal(animal: &Animal) -> Self {
         Self {
-            fitness: animal.satiation as f32,
+            fitness: Some(animal.satiation as f32),
+            behaviour: Some(vec![animal.displacement.x, animal.displacement.y]),
             chromosome: animal.as_chromosome(),
         }
     }
 
+    /// Brings back a bird evaluated earlier - e.g. when resuming from a
+    /// [`ga::Snapshot`] through [`ga::Snapshot::population_with()`].
+    pub fn evaluated(
+        chromosome: ga::Chromosome,
+        fitness: f32,
+        behaviour: Option<Vec<f32>>,
+    ) -> Self {
+        Self {
+            fitness: Some(fitness),
+            behaviour,
+            chromosome,
+        }
+    }
+
     pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
         Animal::from_chromosome(self.chromosome, rng)
     }
@@ -465,7 +587,8 @@ impl AnimalIndividual {
 impl ga::Individual for AnimalIndividual {
     fn create(chromosome: ga::Chromosome) -> Self {
         Self {
-            fitness: 0.0,
+            fitness: None,
+            behaviour: None,
             chromosome,
         }
     }
@@ -475,7 +598,15 @@ impl ga::Individual for AnimalIndividual {
     }
 
     fn fitness(&self) -> f32 {
-        self.fitness
+        self.fitness.unwrap_or(0.0)
+    }
+
+    fn is_evaluated(&self) -> bool {
+        self.fitness.is_some()
+    }
+
+    fn behaviour(&self) -> Option<Vec<f32>> {
+        self.behaviour.clone()
     }
 }
 
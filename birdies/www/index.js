@@ -57,6 +57,10 @@ document.getElementById('train').onclick = function () {
     console.log(simulation.train());
 };
 
+document.getElementById('replay').onclick = function () {
+    simulation.replay_champions();
+};
+
 const viewport = document.getElementById('viewport');
 const viewportWidth = viewport.width;
 const viewportHeight = viewport.height;